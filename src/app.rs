@@ -1,92 +1,519 @@
 use crate::config::Config;
-use crate::timer::PomodoroTimer;
+use crate::config::settings::{ClockStyle, Settings};
+use crate::timer::{PomodoroTimer, TimerPhase};
 use crate::ui::Theme;
+use crate::ui::theme::{user_themes, BUILTIN_THEMES};
+use crate::ui::toast::Toasts;
+use crate::i18n::Locale;
 use crate::notification::NotificationManager;
-use crate::hyprland::StatusWriter;
+use crate::status::StatusWriter;
+use crate::status::sink::StatusSink;
+use crate::hooks::{HookContext, HookEvent, HookRunner};
+use crate::hyprland::{ActivityTracker, FocusEnforcer};
+use crate::history::{History, HistoryEvent};
+use crate::idle::{IdleEvent, IdleMonitor};
+use crate::breaks::StrictBreaks;
+use crate::mpris::MprisController;
+use crate::events::{help_key, parse, Action, Command, Keymap, Palette, PaletteEvent};
+use crate::timer::TimerState;
 use crate::error::Result;
+use crate::log;
+use std::cell::Cell;
+use std::time::Duration;
+use crossterm::event::KeyEvent;
 
 pub struct App {
     pub timer: PomodoroTimer,
     pub theme: Theme,
+    pub clock_style: ClockStyle,
+    pub locale: Locale,
+    pub keymap: Keymap,
     pub show_help: bool,
+    /// First visible line of the help popup
+    pub help_scroll: u16,
+    /// Set while rendering, since how far the help can scroll depends on
+    /// the terminal size
+    pub help_scroll_max: Cell<u16>,
+    pub palette: Palette,
+    pub toasts: Toasts,
+    /// Progress-bar position under the mouse, in percent
+    pub seek_preview: Option<u16>,
     pub running: bool,
+    /// Seconds left before the next phase starts on its own
+    pub auto_start_countdown: Option<u64>,
+    auto_start_breaks: bool,
+    auto_start_focus: bool,
+    auto_start_delay: u64,
     notification_manager: NotificationManager,
     status_writer: StatusWriter,
+    hook_runner: HookRunner,
+    focus_enforcer: Option<FocusEnforcer>,
+    activity_tracker: Option<ActivityTracker>,
+    idle_monitor: Option<IdleMonitor>,
+    discard_idle_time: bool,
+    pub strict_breaks: Option<StrictBreaks>,
+    mpris: Option<MprisController>,
+    history: History,
+    /// Kept for switching profiles from the palette
+    settings: Settings,
 }
 
 impl App {
     pub fn new(config: Config) -> Result<Self> {
-        let timer_settings = &config.settings.timer;
+        let timer_settings = config.settings.effective_timer();
         let timer = PomodoroTimer::new(
             timer_settings.focus_duration,
             timer_settings.short_break_duration,
             timer_settings.long_break_duration,
             timer_settings.cycles_before_long_break,
+            timer_settings.overtime,
         );
 
-        let theme = Theme::from_name(&config.settings.ui.theme);
-        let notification_manager = NotificationManager::new(config.settings.notifications.enabled);
-        let status_writer = StatusWriter::new(config.settings.integrations.hyprland_status_bar)?;
+        let theme = Theme::load(&config.settings.ui)?;
+        let locale = Locale::load(&config.settings.ui)?;
+        let keymap = Keymap::new(&config.settings.keybindings)?;
+        let notification_manager = NotificationManager::new(config.settings.notifications.enabled, locale.clone());
+        let status_writer = StatusWriter::new(&config.settings, &theme)?;
+        let hook_runner = HookRunner::new(config.settings.hooks.clone());
+        let focus_enforcer = FocusEnforcer::new(&config.settings.hyprland, &theme);
+        let activity_tracker = ActivityTracker::new(&config.settings.hyprland);
+        let idle_monitor = IdleMonitor::new(&config.settings.idle);
+        let history = History::new();
+        let strict_breaks = StrictBreaks::new(&config.settings.breaks, &history);
 
         Ok(Self {
             timer,
             theme,
+            clock_style: config.settings.ui.clock_style,
+            locale,
+            keymap,
             show_help: false,
+            help_scroll: 0,
+            help_scroll_max: Cell::new(0),
+            palette: Palette::default(),
+            toasts: Toasts::default(),
+            seek_preview: None,
             running: true,
+            auto_start_countdown: None,
+            auto_start_breaks: timer_settings.auto_start_breaks,
+            auto_start_focus: timer_settings.auto_start_focus,
+            auto_start_delay: timer_settings.auto_start_delay,
             notification_manager,
             status_writer,
+            hook_runner,
+            focus_enforcer,
+            activity_tracker,
+            idle_monitor,
+            discard_idle_time: config.settings.idle.discard_idle_time,
+            strict_breaks,
+            mpris: MprisController::new(&config.settings.mpris),
+            history,
+            settings: config.settings,
         })
     }
 
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.palette.is_open() && !self.break_locked() {
+            match self.palette.handle_key(key) {
+                PaletteEvent::Run(line) => self.run_command(&line),
+                PaletteEvent::Close => self.palette.close(),
+                PaletteEvent::None => {}
+            }
+            return;
+        }
+
+        let action = if self.show_help && !self.break_locked() {
+            help_key(key)
+        } else {
+            self.keymap.action(key)
+        };
+        self.handle_action(action);
+    }
+
+    pub fn handle_action(&mut self, action: Action) {
+        if self.in_strict_break() && !Self::allowed_in_strict_break(action) {
+            return;
+        }
+
+        match action {
+            Action::Quit => self.quit(),
+            Action::TogglePause => self.toggle_pause(),
+            Action::Reset => self.reset(),
+            Action::Skip => self.skip(),
+            Action::ToggleHelp => self.toggle_help(),
+            Action::CancelAutoStart => self.cancel_auto_start(),
+            Action::Adjust(secs) => self.adjust(secs),
+            Action::Idle(secs) => self.handle_idle(IdleEvent::Idle { already: secs.map(Duration::from_secs) }),
+            Action::Active => self.handle_idle(IdleEvent::Active),
+            Action::EmergencySkip => self.emergency_skip(),
+            Action::PostponeBreak => self.postpone_break(),
+            Action::SeekPreview(percent) => self.seek_preview = percent,
+            Action::ScrollHelp(lines) => self.scroll_help(lines),
+            Action::OpenPalette => self.open_palette(),
+            Action::None => {}
+        }
+    }
+
     pub fn toggle_pause(&mut self) {
+        self.auto_start_countdown = None;
+        let current = &self.timer.current_timer;
+        let event = match current.state {
+            TimerState::Running => HookEvent::Pause,
+            _ if current.remaining == current.duration => HookEvent::phase_start(current.phase),
+            _ => HookEvent::Resume,
+        };
         self.timer.toggle();
+        self.emit(event);
         self.update_status();
     }
 
     pub fn reset(&mut self) {
+        self.auto_start_countdown = None;
         self.timer.reset();
         self.update_status();
     }
 
+    pub fn adjust(&mut self, secs: i64) {
+        let applied = self.timer.adjust(secs);
+        if applied != 0 {
+            self.history.record(HistoryEvent::Adjust {
+                phase: self.timer.current_phase().as_str().to_string(),
+                seconds: applied,
+            });
+        }
+        self.update_status();
+    }
+
     pub fn skip(&mut self) {
         let old_phase = self.timer.current_phase();
+        let already_notified = self.timer.current_timer.ran_out;
+        let ended = HookContext::new(&self.timer);
         self.timer.skip();
-        self.notification_manager.send_phase_complete(old_phase);
+        if !already_notified {
+            self.notify_phase_complete(old_phase);
+        }
+        self.emit_with(HookEvent::Skip, &ended);
+        self.emit_with(HookEvent::phase_end(old_phase), &ended);
+        self.schedule_auto_start();
+        self.update_status();
+    }
+
+    /// Publish status to an extra sink, e.g. one that needed async setup.
+    #[cfg_attr(not(feature = "dbus"), allow(dead_code))]
+    pub fn add_status_sink(&mut self, sink: Box<dyn StatusSink>) {
+        self.status_writer.add_sink(sink);
+        self.update_status();
+    }
+
+    /// True while a strict break is in progress or postponed.
+    pub fn in_strict_break(&self) -> bool {
+        self.strict_breaks.is_some() && self.timer.current_phase().is_break()
+    }
+
+    /// True while the strict-break overlay should cover the timer.
+    pub fn break_locked(&self) -> bool {
+        self.in_strict_break() && self.auto_start_countdown.is_none()
+    }
+
+    fn allowed_in_strict_break(action: Action) -> bool {
+        matches!(
+            action,
+            Action::Quit
+                | Action::EmergencySkip
+                | Action::PostponeBreak
+                | Action::Idle(_)
+                | Action::Active
+                | Action::None
+        )
+    }
+
+    pub fn emergency_skip(&mut self) {
+        if !self.in_strict_break() {
+            return;
+        }
+        let allowed = match &mut self.strict_breaks {
+            Some(strict) => strict.use_emergency_skip(&self.history),
+            None => false,
+        };
+        if allowed {
+            self.skip();
+        }
+    }
+
+    pub fn postpone_break(&mut self) {
+        if !self.break_locked() {
+            return;
+        }
+        let Some(strict) = &mut self.strict_breaks else {
+            return;
+        };
+        if !strict.use_postpone(&self.history) {
+            return;
+        }
+        let minutes = strict.postpone_minutes();
+
+        if self.timer.is_running() {
+            self.timer.toggle();
+            self.emit(HookEvent::Pause);
+        }
+        // The break picks up again on its own once the countdown ends
+        self.auto_start_countdown = Some(minutes * 60);
         self.update_status();
     }
 
+    pub fn cancel_auto_start(&mut self) {
+        self.auto_start_countdown = None;
+    }
+
     pub fn tick(&mut self) {
-        let phase_completed = self.timer.tick();
-        if phase_completed {
-            // Get the phase that just completed (before it changed)
-            let completed_phase = match self.timer.current_phase() {
-                crate::timer::TimerPhase::Focus => {
-                    // If we're now in Focus, a break just completed
-                    if self.timer.cycle_count == 0 {
-                        crate::timer::TimerPhase::LongBreak
-                    } else {
-                        crate::timer::TimerPhase::ShortBreak
+        self.toasts.expire();
+
+        if let Some(strict) = &mut self.strict_breaks {
+            strict.roll_over(&self.history);
+        }
+
+        if let Some(tracker) = &mut self.activity_tracker {
+            if self.timer.is_running() && self.timer.current_phase() == TimerPhase::Focus {
+                tracker.tick();
+            } else {
+                tracker.idle();
+            }
+        }
+
+        self.check_idle();
+
+        if let Some(remaining) = self.auto_start_countdown {
+            if remaining <= 1 {
+                self.auto_start_countdown = None;
+                self.start_phase();
+            } else {
+                self.auto_start_countdown = Some(remaining - 1);
+            }
+            self.update_status();
+            return;
+        }
+
+        if let Some(completed_phase) = self.timer.tick() {
+            self.notify_phase_complete(completed_phase);
+            // In overtime the phase carries on until skipped
+            if !self.timer.current_timer.in_overtime() {
+                let ended = HookContext::new(&self.timer);
+                self.timer.skip();
+                self.emit_with(HookEvent::phase_end(completed_phase), &ended);
+                self.schedule_auto_start();
+            }
+        }
+        self.update_status();
+    }
+
+    /// Start the new phase now or after the grace period, if auto-start
+    /// is enabled for it.
+    fn schedule_auto_start(&mut self) {
+        // Strict breaks can't be put off by simply not starting them
+        if self.in_strict_break() {
+            self.start_phase();
+            return;
+        }
+
+        let enabled = match self.timer.current_phase() {
+            TimerPhase::Focus => self.auto_start_focus,
+            TimerPhase::ShortBreak | TimerPhase::LongBreak => self.auto_start_breaks,
+        };
+        if !enabled {
+            return;
+        }
+
+        if self.auto_start_delay == 0 {
+            self.start_phase();
+        } else {
+            self.auto_start_countdown = Some(self.auto_start_delay);
+        }
+    }
+
+    fn start_phase(&mut self) {
+        if self.timer.is_running() {
+            return;
+        }
+        self.timer.toggle();
+        self.emit(HookEvent::phase_start(self.timer.current_phase()));
+    }
+
+    fn handle_idle(&mut self, event: IdleEvent) {
+        if let Some(monitor) = &mut self.idle_monitor {
+            monitor.handle(event);
+        }
+        self.check_idle();
+    }
+
+    /// Pause a running focus phase once the user has been away too long.
+    fn check_idle(&mut self) {
+        let Some(idle) = self.idle_monitor.as_mut().and_then(|m| m.idle_for()) else {
+            return;
+        };
+        if !self.timer.is_running() || self.timer.current_phase() != TimerPhase::Focus {
+            return;
+        }
+
+        self.timer.toggle();
+        if self.discard_idle_time {
+            // Only time counted since the timer last started can be taken
+            // back; idling that began earlier wasn't credited to this run
+            let counted = self.timer.current_timer.running_for;
+            self.timer.current_timer.rewind(idle.min(counted));
+        }
+        self.notification_manager.send_idle_paused(idle.as_secs() / 60);
+        self.emit(HookEvent::Pause);
+        self.update_status();
+    }
+
+    /// Share of the current (or last) focus session spent away from
+    /// distracting windows, when activity tracking is on.
+    pub fn focus_score(&self) -> Option<u8> {
+        self.activity_tracker.as_ref()?.focus_score()
+    }
+
+    fn notify_phase_complete(&self, phase: TimerPhase) {
+        let score = match phase {
+            TimerPhase::Focus => self.focus_score(),
+            TimerPhase::ShortBreak | TimerPhase::LongBreak => None,
+        };
+        self.notification_manager.send_phase_complete(phase, score);
+    }
+
+    /// Tell hooks and desktop integrations about a timer event.
+    fn emit(&mut self, event: HookEvent) {
+        self.emit_with(event, &HookContext::new(&self.timer));
+    }
+
+    /// `emit` for an event about a phase the timer has already left.
+    fn emit_with(&mut self, event: HookEvent, context: &HookContext) {
+        self.hook_runner.fire(event, context);
+
+        if let Some(mpris) = &self.mpris {
+            match event {
+                HookEvent::FocusStart => mpris.focus_started(),
+                HookEvent::FocusEnd => mpris.focus_ended(),
+                _ => {}
+            }
+        }
+
+        if let (Some(strict), HookEvent::BreakStart) = (&self.strict_breaks, event) {
+            strict.run_locker();
+        }
+
+        if let Some(tracker) = &mut self.activity_tracker {
+            match event {
+                HookEvent::FocusStart => tracker.start_session(),
+                HookEvent::FocusEnd => {
+                    if let Some(report) = tracker.report() {
+                        self.history.record(HistoryEvent::FocusActivity {
+                            score: report.score,
+                            distracted_seconds: report.distracted.as_secs(),
+                            windows: report.per_class,
+                        });
                     }
                 }
-                crate::timer::TimerPhase::ShortBreak | crate::timer::TimerPhase::LongBreak => {
-                    // If we're in a break, focus just completed
-                    crate::timer::TimerPhase::Focus
-                }
-            };
-            self.notification_manager.send_phase_complete(completed_phase);
+                _ => {}
+            }
+        }
+
+        if let Some(enforcer) = &mut self.focus_enforcer {
+            match event {
+                HookEvent::FocusStart => enforcer.start_focus(),
+                HookEvent::FocusEnd => enforcer.end_focus(),
+                HookEvent::BreakStart => enforcer.set_border(self.timer.current_phase()),
+                _ => {}
+            }
         }
-        self.update_status();
     }
 
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    fn scroll_help(&mut self, lines: i16) {
+        let scrolled = (self.help_scroll as i32 + lines as i32).max(0) as u16;
+        self.help_scroll = scrolled.min(self.help_scroll_max.get());
+    }
+
+    pub fn open_palette(&mut self) {
+        self.show_help = false;
+        let mut themes: Vec<String> = BUILTIN_THEMES.iter().map(|name| name.to_string()).collect();
+        themes.extend(user_themes());
+        let mut profiles: Vec<String> = self.settings.profiles.keys().cloned().collect();
+        profiles.sort();
+        self.palette.open(vec![("theme", themes), ("profile", profiles)]);
+    }
+
+    /// Runs a line from the palette. It stays open showing the error if
+    /// the command fails.
+    fn run_command(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            self.palette.close();
+            return;
+        }
+
+        let result = match parse(line) {
+            Some(Command::Action(action)) => {
+                // Close first, so `help` and `palette` itself open cleanly
+                self.palette.finish(line);
+                self.handle_action(action);
+                return;
+            }
+            Some(Command::Theme(name)) => self.switch_theme(&name),
+            Some(Command::Profile(name)) => self.switch_profile(&name),
+            None => Err(self.locale.format("palette.unknown", &[("command", &line.trim())])),
+        };
+        match result {
+            Ok(()) => self.palette.finish(line),
+            Err(message) => self.palette.fail(message),
+        }
+    }
+
+    pub fn switch_theme(&mut self, name: &str) -> std::result::Result<(), String> {
+        self.theme = Theme::from_name(name).map_err(|e| e.to_string())?;
+        log::info!("{}", self.locale.format("palette.theme_switched", &[("name", &name)]));
+        Ok(())
+    }
+
+    /// Applies a `[profiles.<name>]` table's phase lengths and auto-start
+    /// settings. A phase already under way keeps its length.
+    pub fn switch_profile(&mut self, name: &str) -> std::result::Result<(), String> {
+        let timer_settings = self
+            .settings
+            .timer_with_profile(name)
+            .ok_or_else(|| self.locale.format("palette.unknown_profile", &[("name", &name)]))?;
+
+        self.timer.set_durations(
+            timer_settings.focus_duration,
+            timer_settings.short_break_duration,
+            timer_settings.long_break_duration,
+            timer_settings.cycles_before_long_break,
+            timer_settings.overtime,
+        );
+        self.auto_start_breaks = timer_settings.auto_start_breaks;
+        self.auto_start_focus = timer_settings.auto_start_focus;
+        self.auto_start_delay = timer_settings.auto_start_delay;
+        self.status_writer.set_profile(timer_settings.profile);
+        self.update_status();
+        log::info!("{}", self.locale.format("palette.profile_switched", &[("name", &name)]));
+        Ok(())
     }
 
     pub fn quit(&mut self) {
         self.running = false;
     }
 
-    fn update_status(&self) {
+    /// Undo what integrations changed, for the ones that need to wait for
+    /// it. The rest do so when dropped.
+    pub async fn shutdown(&mut self) {
+        if let Some(mpris) = self.mpris.take() {
+            mpris.shutdown().await;
+        }
+    }
+
+    fn update_status(&mut self) {
         self.status_writer.update(&self.timer);
     }
 }
@@ -0,0 +1,106 @@
+use std::process::Stdio;
+use chrono::{Local, NaiveDate};
+use tokio::process::Command;
+use crate::config::settings::BreakSettings;
+use crate::history::{History, HistoryEvent};
+use crate::log;
+
+/// Strict-break mode: breaks can't be paused or skipped, except through a
+/// small daily allowance of emergency skips and postponements.
+pub struct StrictBreaks {
+    settings: BreakSettings,
+    emergency_skips_used: u32,
+    postpones_used: u32,
+    counted_on: NaiveDate,
+}
+
+impl StrictBreaks {
+    /// Returns `None` unless strict mode is enabled.
+    pub fn new(settings: &BreakSettings, history: &History) -> Option<Self> {
+        if !settings.strict {
+            return None;
+        }
+
+        let mut strict = Self {
+            settings: settings.clone(),
+            emergency_skips_used: 0,
+            postpones_used: 0,
+            counted_on: Local::now().date_naive(),
+        };
+        strict.count_today(history);
+        Some(strict)
+    }
+
+    fn count_today(&mut self, history: &History) {
+        let today: Vec<_> = history.entries().into_iter().filter(|e| e.is_today()).collect();
+        self.emergency_skips_used = today
+            .iter()
+            .filter(|e| matches!(e.event, HistoryEvent::EmergencySkip))
+            .count() as u32;
+        self.postpones_used = today
+            .iter()
+            .filter(|e| matches!(e.event, HistoryEvent::PostponeBreak { .. }))
+            .count() as u32;
+        self.counted_on = Local::now().date_naive();
+    }
+
+    /// Start each day with a fresh allowance.
+    pub fn roll_over(&mut self, history: &History) {
+        if Local::now().date_naive() != self.counted_on {
+            self.count_today(history);
+        }
+    }
+
+    pub fn emergency_skips_left(&self) -> u32 {
+        self.settings.emergency_skips_per_day.saturating_sub(self.emergency_skips_used)
+    }
+
+    pub fn postpones_left(&self) -> u32 {
+        self.settings.postpones_per_day.saturating_sub(self.postpones_used)
+    }
+
+    pub fn postpone_minutes(&self) -> u64 {
+        self.settings.postpone_minutes
+    }
+
+    /// Spend an emergency skip, returning false when none are left.
+    pub fn use_emergency_skip(&mut self, history: &History) -> bool {
+        if self.emergency_skips_left() == 0 {
+            return false;
+        }
+        self.emergency_skips_used += 1;
+        history.record(HistoryEvent::EmergencySkip);
+        true
+    }
+
+    /// Spend a postponement, returning false when none are left.
+    pub fn use_postpone(&mut self, history: &History) -> bool {
+        if self.postpones_left() == 0 {
+            return false;
+        }
+        self.postpones_used += 1;
+        history.record(HistoryEvent::PostponeBreak {
+            minutes: self.settings.postpone_minutes,
+        });
+        true
+    }
+
+    /// Launch the configured screen locker, if any, without waiting on it.
+    /// Tokio reaps the process once it exits.
+    pub fn run_locker(&self) {
+        let Some(command) = self.settings.locker_command.as_deref() else {
+            return;
+        };
+
+        if let Err(e) = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            log::warning!("Failed to run locker command: {}", e);
+        }
+    }
+}
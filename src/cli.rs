@@ -0,0 +1,47 @@
+use clap::{Parser, Subcommand};
+use crate::log::Level;
+
+#[derive(Debug, Parser)]
+#[command(name = "devchron", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Lowest level written to the log file: debug, info, warning or error
+    #[arg(long, global = true, env = "DEVCHRON_LOG", value_name = "LEVEL")]
+    pub log_level: Option<Level>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Send a command to the running timer, e.g. `skip` or `extend 5`
+    Ctl {
+        /// toggle, reset, skip, cancel, quit, extend <min>, shorten <min>
+        #[arg(required = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Stream status as Waybar custom-module JSON, one line per change
+    Waybar,
+    /// Print the current status once for a status bar
+    Status {
+        /// json, waybar, polybar, i3blocks, eww or tmux
+        #[arg(short, long, default_value = "json")]
+        format: String,
+    },
+    /// List or preview color themes
+    Themes {
+        #[command(subcommand)]
+        command: ThemesCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ThemesCommand {
+    /// List built-in themes and those in ~/.config/devchron/themes/
+    List,
+    /// Show a theme's colors in the terminal
+    Preview {
+        /// Theme to show; defaults to the configured one
+        name: Option<String>,
+    },
+}
@@ -1,6 +1,7 @@
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Settings {
     #[serde(default)]
     pub timer: TimerSettings,
@@ -13,16 +14,52 @@ pub struct Settings {
     
     #[serde(default)]
     pub integrations: IntegrationSettings,
+    
+    #[serde(default)]
+    pub hooks: HookSettings,
+    
+    #[serde(default)]
+    pub waybar: WaybarSettings,
+    
+    #[serde(default)]
+    pub hyprland: HyprlandSettings,
+    
+    #[serde(default)]
+    pub idle: IdleSettings,
+    
+    #[serde(default)]
+    pub breaks: BreakSettings,
+    
+    #[serde(default)]
+    pub mpris: MprisSettings,
+    
+    /// Keys per command, e.g. `skip = ["s", "ctrl-n"]`. Commands left out
+    /// keep their default keys.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<String, KeySpec>,
+    
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, ProfileSettings>,
 }
 
-impl Default for Settings {
-    fn default() -> Self {
-        Self {
-            timer: TimerSettings::default(),
-            notifications: NotificationSettings::default(),
-            ui: UiSettings::default(),
-            integrations: IntegrationSettings::default(),
+impl Settings {
+    /// Timer settings with the active profile's overrides applied.
+    pub fn effective_timer(&self) -> TimerSettings {
+        let mut timer = self.timer.clone();
+        let profile = timer.profile.as_ref().and_then(|name| self.profiles.get(name));
+        if let Some(profile) = profile {
+            profile.apply(&mut timer);
         }
+        timer
+    }
+
+    /// Timer settings with the named profile applied instead of the
+    /// configured one, `None` if there's no such profile.
+    pub fn timer_with_profile(&self, name: &str) -> Option<TimerSettings> {
+        let mut timer = self.timer.clone();
+        self.profiles.get(name)?.apply(&mut timer);
+        timer.profile = Some(name.to_string());
+        Some(timer)
     }
 }
 
@@ -39,6 +76,23 @@ pub struct TimerSettings {
     
     #[serde(default = "default_cycles")]
     pub cycles_before_long_break: u32,
+    
+    #[serde(default)]
+    pub auto_start_breaks: bool,
+    
+    #[serde(default)]
+    pub auto_start_focus: bool,
+    
+    /// Seconds to count down before an auto-started phase begins
+    #[serde(default = "default_auto_start_delay")]
+    pub auto_start_delay: u64,
+    
+    /// Keep counting past zero in focus instead of forcing a break
+    #[serde(default)]
+    pub overtime: bool,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 impl Default for TimerSettings {
@@ -48,10 +102,56 @@ impl Default for TimerSettings {
             short_break_duration: default_short_break(),
             long_break_duration: default_long_break(),
             cycles_before_long_break: default_cycles(),
+            auto_start_breaks: false,
+            auto_start_focus: false,
+            auto_start_delay: default_auto_start_delay(),
+            overtime: false,
+            profile: None,
         }
     }
 }
 
+/// Per-profile overrides for `[timer]`. Unset fields keep the global value.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProfileSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_duration: Option<u64>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_break_duration: Option<u64>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_break_duration: Option<u64>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycles_before_long_break: Option<u32>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_start_breaks: Option<bool>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_start_focus: Option<bool>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_start_delay: Option<u64>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overtime: Option<bool>,
+}
+
+impl ProfileSettings {
+    fn apply(&self, timer: &mut TimerSettings) {
+        if let Some(v) = self.focus_duration { timer.focus_duration = v; }
+        if let Some(v) = self.short_break_duration { timer.short_break_duration = v; }
+        if let Some(v) = self.long_break_duration { timer.long_break_duration = v; }
+        if let Some(v) = self.cycles_before_long_break { timer.cycles_before_long_break = v; }
+        if let Some(v) = self.auto_start_breaks { timer.auto_start_breaks = v; }
+        if let Some(v) = self.auto_start_focus { timer.auto_start_focus = v; }
+        if let Some(v) = self.auto_start_delay { timer.auto_start_delay = v; }
+        if let Some(v) = self.overtime { timer.overtime = v; }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NotificationSettings {
     #[serde(default = "default_true")]
@@ -72,36 +172,406 @@ impl Default for NotificationSettings {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UiSettings {
+    /// A built-in theme or the name of a file in `~/.config/devchron/themes/`
     #[serde(default = "default_theme")]
     pub theme: String,
+    
+    /// Colors overriding the selected theme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_theme: Option<ThemeColors>,
+    
+    #[serde(default)]
+    pub clock_style: ClockStyle,
+    
+    /// Language of labels and notifications, e.g. "en" or "ja"
+    #[serde(default = "default_language")]
+    pub language: String,
+    
+    /// Shown before each label as `焦 点 [FOCUS]`; empty for one language
+    #[serde(default = "default_secondary_language")]
+    pub secondary_language: String,
+    
+    /// Capture the mouse for clicking controls; turn off to keep the
+    /// terminal's own text selection
+    #[serde(default = "default_true")]
+    pub mouse: bool,
 }
 
 impl Default for UiSettings {
     fn default() -> Self {
         Self {
             theme: default_theme(),
+            custom_theme: None,
+            clock_style: ClockStyle::default(),
+            language: default_language(),
+            secondary_language: default_secondary_language(),
+            mouse: true,
         }
     }
 }
 
+/// How the remaining time is drawn. The large styles fall back to `text`
+/// when the terminal is too small for them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockStyle {
+    /// A single line of full-width digits
+    #[default]
+    Text,
+    Block,
+    Braille,
+    #[serde(alias = "7-segment", alias = "7segment")]
+    SevenSegment,
+    Figlet,
+}
+
+/// Theme colors as `#rrggbb`, a name like `lightred`, or a 256-color index.
+/// Unset fields keep the base theme's color.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ThemeColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_break_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_break_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overtime_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    
+    /// Defaults to the current phase color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_filled: Option<String>,
+    
+    /// Defaults to the current phase color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_empty: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning_color: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_color: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct IntegrationSettings {
     #[serde(default = "default_true")]
     pub hyprland_status_bar: bool,
+    
+    /// Where to write status.json. Supports `~/` and `$VARS`, e.g.
+    /// `$XDG_RUNTIME_DIR/devchron/status.json` to keep it on tmpfs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_path: Option<String>,
+    
+    /// Extra status files written alongside status.json on every update
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_outputs: Vec<StatusOutputSettings>,
 }
 
 impl Default for IntegrationSettings {
     fn default() -> Self {
         Self {
             hyprland_status_bar: true,
+            status_path: None,
+            status_outputs: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatusOutputSettings {
+    /// json, waybar, polybar, i3blocks, eww or tmux
+    pub format: String,
+    /// Destination file; supports `~/` and `$VARS` like `status_path`
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HookSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_focus_start: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_focus_end: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_break_start: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_break_end: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_pause: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_resume: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_skip: Option<String>,
+    
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_secs: u64,
+}
+
+impl Default for HookSettings {
+    fn default() -> Self {
+        Self {
+            on_focus_start: None,
+            on_focus_end: None,
+            on_break_start: None,
+            on_break_end: None,
+            on_pause: None,
+            on_resume: None,
+            on_skip: None,
+            timeout_secs: default_hook_timeout(),
+        }
+    }
+}
+
+/// Output of `devchron waybar`. Formats accept `{icon}`, `{time}`,
+/// `{phase}`, `{session}`, `{percentage}` and `{today}`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaybarSettings {
+    #[serde(default = "default_waybar_format")]
+    pub format: String,
+    
+    #[serde(default = "default_waybar_format_paused")]
+    pub format_paused: String,
+    
+    #[serde(default = "default_waybar_format_inactive")]
+    pub format_inactive: String,
+    
+    /// How often to check the status file, in milliseconds
+    #[serde(default = "default_waybar_interval")]
+    pub interval_ms: u64,
+}
+
+impl Default for WaybarSettings {
+    fn default() -> Self {
+        Self {
+            format: default_waybar_format(),
+            format_paused: default_waybar_format_paused(),
+            format_inactive: default_waybar_format_inactive(),
+            interval_ms: default_waybar_interval(),
+        }
+    }
+}
+
+/// Hyprland focus enforcement over the compositor's IPC socket.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HyprlandSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Workspace to switch to when focus starts, e.g. "9" or "name:focus"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_workspace: Option<String>,
+    
+    /// Window classes to dim or hide during focus (case-insensitive)
+    #[serde(default)]
+    pub blocklist: Vec<String>,
+    
+    #[serde(default)]
+    pub blocklist_action: BlocklistAction,
+    
+    #[serde(default = "default_dim_opacity")]
+    pub dim_opacity: f64,
+    
+    /// Color the active window border to match the current phase
+    #[serde(default = "default_true")]
+    pub phase_borders: bool,
+    
+    /// Follow the active window during focus and compute a focus score
+    #[serde(default)]
+    pub track_activity: bool,
+    
+    /// Window classes that count as distractions (case-insensitive)
+    #[serde(default)]
+    pub distracting_classes: Vec<String>,
+    
+    /// Title fragments that count as distractions, e.g. "reddit"
+    #[serde(default)]
+    pub distracting_titles: Vec<String>,
+}
+
+impl Default for HyprlandSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            focus_workspace: None,
+            blocklist: Vec::new(),
+            blocklist_action: BlocklistAction::default(),
+            dim_opacity: default_dim_opacity(),
+            phase_borders: true,
+            track_activity: false,
+            distracting_classes: Vec::new(),
+            distracting_titles: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlocklistAction {
+    #[default]
+    Dim,
+    Hide,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IdleSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// "external" (driven by `devchron ctl idle`/`active`) or "wayland"
+    #[serde(default = "default_idle_backend")]
+    pub backend: String,
+    
+    /// Minutes of inactivity before focus is paused
+    #[serde(default = "default_idle_timeout")]
+    pub timeout_minutes: u64,
+    
+    /// Give back the idle time instead of counting it as focus
+    #[serde(default = "default_true")]
+    pub discard_idle_time: bool,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_idle_backend(),
+            timeout_minutes: default_idle_timeout(),
+            discard_idle_time: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BreakSettings {
+    /// Start breaks immediately and block the timer until they end
+    #[serde(default)]
+    pub strict: bool,
+    
+    /// Screen locker to run when a strict break starts, e.g. "hyprlock"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locker_command: Option<String>,
+    
+    #[serde(default = "default_emergency_skips")]
+    pub emergency_skips_per_day: u32,
+    
+    #[serde(default = "default_postpone_minutes")]
+    pub postpone_minutes: u64,
+    
+    #[serde(default = "default_postpones")]
+    pub postpones_per_day: u32,
+}
+
+impl Default for BreakSettings {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            locker_command: None,
+            emergency_skips_per_day: default_emergency_skips(),
+            postpone_minutes: default_postpone_minutes(),
+            postpones_per_day: default_postpones(),
+        }
+    }
+}
+
+/// Media player control over MPRIS (needs the `mpris` feature).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MprisSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    #[serde(default)]
+    pub mode: MprisMode,
+    
+    /// Players to control, e.g. ["spotify", "firefox"]; empty means all
+    #[serde(default)]
+    pub players: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MprisMode {
+    /// Pause whatever is playing when focus starts, resume it afterwards
+    PauseDuringFocus,
+    /// Pause when focus ends, resume when the next focus starts
+    #[default]
+    PauseOnBreak,
+    /// Play only during focus, e.g. a focus playlist
+    PlayDuringFocus,
+}
+
 // Default value functions
 fn default_focus_duration() -> u64 { 25 }
 fn default_short_break() -> u64 { 5 }
 fn default_long_break() -> u64 { 15 }
 fn default_cycles() -> u32 { 4 }
+fn default_auto_start_delay() -> u64 { 5 }
 fn default_theme() -> String { "nord".to_string() }
+fn default_language() -> String { "en".to_string() }
+fn default_secondary_language() -> String { "ja".to_string() }
 fn default_true() -> bool { true }
+fn default_hook_timeout() -> u64 { 10 }
+fn default_waybar_format() -> String { "{icon} {time}".to_string() }
+fn default_waybar_format_paused() -> String { "⏸ {time}".to_string() }
+fn default_waybar_format_inactive() -> String { "⏸".to_string() }
+fn default_waybar_interval() -> u64 { 500 }
+fn default_dim_opacity() -> f64 { 0.3 }
+fn default_idle_backend() -> String { "external".to_string() }
+fn default_idle_timeout() -> u64 { 5 }
+fn default_emergency_skips() -> u32 { 2 }
+fn default_postpone_minutes() -> u64 { 5 }
+fn default_postpones() -> u32 { 3 }
+
+/// One key or a list of keys in `[keybindings]`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    pub fn keys(&self) -> &[String] {
+        match self {
+            KeySpec::One(key) => std::slice::from_ref(key),
+            KeySpec::Many(keys) => keys,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_writes_plain_numbers() {
+        let written = toml::to_string_pretty(&Settings::default()).unwrap();
+        assert!(written.contains("dim_opacity = 0.3\n"), "{}", written);
+    }
+}
@@ -0,0 +1,303 @@
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use zbus::object_server::{InterfaceRef, SignalEmitter};
+use zbus::{connection, interface, Connection};
+use crate::error::{Error, Result};
+use crate::events::Action;
+use crate::status::StatusUpdate;
+use crate::status::sink::StatusSink;
+use crate::log;
+
+pub const BUS_NAME: &str = "org.devchron.Timer";
+pub const OBJECT_PATH: &str = "/org/devchron/Timer";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TimerProperties {
+    phase: String,
+    remaining: u64,
+    running: bool,
+    session: String,
+}
+
+impl TimerProperties {
+    fn from_status(status: &StatusUpdate) -> Self {
+        Self {
+            phase: status.phase.clone(),
+            remaining: status.remaining_seconds,
+            running: status.is_running,
+            session: status.session.clone(),
+        }
+    }
+}
+
+/// The `org.devchron.Timer` interface. Method calls are forwarded to the
+/// app as `Action`s, the same way control socket commands are.
+struct TimerInterface {
+    actions: UnboundedSender<Action>,
+    properties: TimerProperties,
+}
+
+impl TimerInterface {
+    fn send(&self, action: Action) -> zbus::fdo::Result<()> {
+        self.actions
+            .send(action)
+            .map_err(|_| zbus::fdo::Error::Failed("timer is shutting down".to_string()))
+    }
+}
+
+#[interface(name = "org.devchron.Timer")]
+impl TimerInterface {
+    fn toggle(&self) -> zbus::fdo::Result<()> {
+        self.send(Action::TogglePause)
+    }
+
+    fn skip(&self) -> zbus::fdo::Result<()> {
+        self.send(Action::Skip)
+    }
+
+    fn reset(&self) -> zbus::fdo::Result<()> {
+        self.send(Action::Reset)
+    }
+
+    /// Lengthen (positive) or shorten (negative) the current phase.
+    fn extend(&self, seconds: i64) -> zbus::fdo::Result<()> {
+        self.send(Action::Adjust(seconds))
+    }
+
+    #[zbus(property)]
+    fn phase(&self) -> &str {
+        &self.properties.phase
+    }
+
+    /// Seconds left in the current phase
+    #[zbus(property)]
+    fn remaining(&self) -> u64 {
+        self.properties.remaining
+    }
+
+    #[zbus(property)]
+    fn running(&self) -> bool {
+        self.properties.running
+    }
+
+    #[zbus(property)]
+    fn session(&self) -> &str {
+        &self.properties.session
+    }
+
+    /// Emitted as `PhaseChanged`; renamed in Rust to avoid clashing with
+    /// the generated `Phase` property notifier.
+    #[zbus(signal, name = "PhaseChanged")]
+    async fn phase_transition(emitter: &SignalEmitter<'_>, phase: &str) -> zbus::Result<()>;
+}
+
+/// Exports the timer on the session bus and keeps its properties in sync
+/// with each status update.
+pub struct DbusSink {
+    updates: UnboundedSender<TimerProperties>,
+    // Dropping the connection releases the bus name
+    _connection: Connection,
+}
+
+impl DbusSink {
+    pub async fn start(actions: UnboundedSender<Action>) -> Result<Self> {
+        let builder = connection::Builder::session()
+            .and_then(|b| b.name(BUS_NAME))
+            .map_err(dbus_error)?;
+        Self::serve(builder, actions).await
+    }
+
+    /// Export the interface on the connection `builder` makes.
+    async fn serve(builder: connection::Builder<'_>, actions: UnboundedSender<Action>) -> Result<Self> {
+        let interface = TimerInterface {
+            actions,
+            properties: TimerProperties::default(),
+        };
+
+        let connection = builder
+            .serve_at(OBJECT_PATH, interface)
+            .map_err(dbus_error)?
+            .build()
+            .await
+            .map_err(dbus_error)?;
+
+        let iface_ref = connection
+            .object_server()
+            .interface::<_, TimerInterface>(OBJECT_PATH)
+            .await
+            .map_err(dbus_error)?;
+
+        let (updates, rx) = mpsc::unbounded_channel();
+        tokio::spawn(publish_updates(iface_ref, rx));
+
+        Ok(Self {
+            updates,
+            _connection: connection,
+        })
+    }
+}
+
+impl StatusSink for DbusSink {
+    fn write(&mut self, status: &StatusUpdate) -> std::io::Result<()> {
+        self.updates
+            .send(TimerProperties::from_status(status))
+            .map_err(|_| std::io::Error::other("D-Bus publisher stopped"))
+    }
+
+    fn describe(&self) -> String {
+        format!("D-Bus {}", BUS_NAME)
+    }
+}
+
+/// Apply updates one at a time so property and signal order is preserved.
+async fn publish_updates(
+    iface_ref: InterfaceRef<TimerInterface>,
+    mut updates: UnboundedReceiver<TimerProperties>,
+) {
+    while let Some(new) = updates.recv().await {
+        let emitter = iface_ref.signal_emitter();
+        let mut iface = iface_ref.get_mut().await;
+        let old = std::mem::replace(&mut iface.properties, new.clone());
+
+        let result = async {
+            if old.phase != new.phase {
+                iface.phase_changed(emitter).await?;
+                TimerInterface::phase_transition(emitter, &new.phase).await?;
+            }
+            if old.remaining != new.remaining {
+                iface.remaining_changed(emitter).await?;
+            }
+            if old.running != new.running {
+                iface.running_changed(emitter).await?;
+            }
+            if old.session != new.session {
+                iface.session_changed(emitter).await?;
+            }
+            zbus::Result::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            log::warning!("Failed to publish D-Bus update: {}", e);
+        }
+    }
+}
+
+fn dbus_error(e: zbus::Error) -> Error {
+    Error::Ipc(format!("D-Bus: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+    use std::future::poll_fn;
+    use std::pin::Pin;
+    use zbus::export::futures_core::Stream;
+    use zbus::message::Type;
+    use zbus::zvariant::OwnedValue;
+    use zbus::{Guid, MessageStream};
+    use crate::timer::PomodoroTimer;
+
+    /// A sink served over a socket pair, and a peer connection to it.
+    async fn connect() -> (DbusSink, Connection, UnboundedReceiver<Action>) {
+        let (server, client) = UnixStream::pair().unwrap();
+        let (actions, received) = mpsc::unbounded_channel();
+        let server = connection::Builder::unix_stream(server).server(Guid::generate()).unwrap().p2p();
+        let client = connection::Builder::unix_stream(client).p2p().build();
+        let (sink, client) = tokio::join!(DbusSink::serve(server, actions), client);
+        (sink.unwrap(), client.unwrap(), received)
+    }
+
+    async fn property<T: TryFrom<OwnedValue>>(client: &Connection, name: &str) -> T {
+        let reply = client
+            .call_method(
+                None::<&str>,
+                OBJECT_PATH,
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &("org.devchron.Timer", name),
+            )
+            .await
+            .unwrap();
+        let value: OwnedValue = reply.body().deserialize().unwrap();
+        T::try_from(value).ok().unwrap()
+    }
+
+    async fn call(client: &Connection, method: &str, body: &(impl serde::Serialize + zbus::zvariant::DynamicType)) {
+        client
+            .call_method(None::<&str>, OBJECT_PATH, Some("org.devchron.Timer"), method, body)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn publishes_status_as_properties() {
+        let (mut sink, client, _actions) = connect().await;
+        let mut timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        timer.toggle();
+        sink.write(&StatusUpdate::from_timer(&timer, None)).unwrap();
+
+        // Updates are applied in the background
+        for _ in 0..100 {
+            if property::<u64>(&client, "Remaining").await == 1500 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(property::<String>(&client, "Phase").await, "focus");
+        assert_eq!(property::<u64>(&client, "Remaining").await, 1500);
+        assert!(property::<bool>(&client, "Running").await);
+        assert_eq!(property::<String>(&client, "Session").await, "1/4");
+    }
+
+    #[tokio::test]
+    async fn meets_the_sink_contract() {
+        let (mut sink, client, _actions) = connect().await;
+        let client = &client;
+        crate::status::sink::contract::check(&mut sink, || async move {
+            Some((property(client, "Phase").await, property(client, "Remaining").await))
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn signals_phase_changes() {
+        let (mut sink, client, _actions) = connect().await;
+        let mut messages = MessageStream::from(&client);
+        let mut timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        sink.write(&StatusUpdate::from_timer(&timer, None)).unwrap();
+        timer.skip();
+        sink.write(&StatusUpdate::from_timer(&timer, None)).unwrap();
+
+        let phase = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(message)) = poll_fn(|cx| Pin::new(&mut messages).poll_next(cx)).await {
+                let header = message.header();
+                if header.message_type() == Type::Signal
+                    && header.member().map(|m| m.as_str()) == Some("PhaseChanged")
+                {
+                    let phase: String = message.body().deserialize().unwrap();
+                    if phase == "short_break" {
+                        return phase;
+                    }
+                }
+            }
+            panic!("connection closed");
+        })
+        .await
+        .expect("PhaseChanged for the break");
+        assert_eq!(phase, "short_break");
+    }
+
+    #[tokio::test]
+    async fn methods_become_actions() {
+        let (_sink, client, mut actions) = connect().await;
+        call(&client, "Toggle", &()).await;
+        call(&client, "Extend", &(-120i64,)).await;
+        call(&client, "Skip", &()).await;
+
+        assert_eq!(actions.recv().await, Some(Action::TogglePause));
+        assert_eq!(actions.recv().await, Some(Action::Adjust(-120)));
+        assert_eq!(actions.recv().await, Some(Action::Skip));
+    }
+}
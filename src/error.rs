@@ -6,6 +6,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Io(std::io::Error),
     Config(String),
+    Ipc(String),
 }
 
 impl fmt::Display for Error {
@@ -13,6 +14,7 @@ impl fmt::Display for Error {
         match self {
             Error::Io(err) => write!(f, "IO error: {}", err),
             Error::Config(msg) => write!(f, "Config error: {}", msg),
+            Error::Ipc(msg) => write!(f, "IPC error: {}", msg),
         }
     }
 }
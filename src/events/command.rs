@@ -0,0 +1,138 @@
+use super::keyboard::Action;
+
+/// Parse a text command such as `skip` or `extend 5` into an `Action`.
+///
+/// Shared by the control socket and anything else that accepts typed
+/// commands. Durations are minutes unless suffixed with `s`.
+pub fn parse_command(input: &str) -> Option<Action> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next()?.to_lowercase();
+    let arg = parts.next();
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    match (name.as_str(), arg) {
+        ("toggle" | "pause" | "start", None) => Some(Action::TogglePause),
+        ("reset", None) => Some(Action::Reset),
+        ("skip", None) => Some(Action::Skip),
+        ("quit", None) => Some(Action::Quit),
+        ("help", None) => Some(Action::ToggleHelp),
+        ("cancel", None) => Some(Action::CancelAutoStart),
+        ("extend", Some(arg)) => parse_seconds(arg).map(Action::Adjust),
+        ("shorten", Some(arg)) => parse_seconds(arg).and_then(i64::checked_neg).map(Action::Adjust),
+        ("idle", None) => Some(Action::Idle(None)),
+        ("idle", Some(arg)) => parse_seconds(arg)
+            .filter(|secs| *secs >= 0)
+            .map(|secs| Action::Idle(Some(secs as u64))),
+        ("active", None) => Some(Action::Active),
+        ("emergency-skip", None) => Some(Action::EmergencySkip),
+        ("postpone", None) => Some(Action::PostponeBreak),
+        ("palette", None) => Some(Action::OpenPalette),
+        _ => None,
+    }
+}
+
+/// A command typed into the palette: a timer action, or one that changes
+/// settings while running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Action(Action),
+    Theme(String),
+    Profile(String),
+}
+
+/// Commands offered by the palette, with a placeholder for their argument.
+pub const COMMANDS: &[(&str, Option<&str>)] = &[
+    ("toggle", None),
+    ("reset", None),
+    ("skip", None),
+    ("cancel", None),
+    ("extend", Some("<minutes>")),
+    ("shorten", Some("<minutes>")),
+    ("emergency-skip", None),
+    ("postpone", None),
+    ("theme", Some("<name>")),
+    ("profile", Some("<name>")),
+    ("help", None),
+    ("quit", None),
+];
+
+/// `parse_command` plus the commands only the palette takes.
+pub fn parse(input: &str) -> Option<Command> {
+    if let Some(action) = parse_command(input) {
+        return Some(Command::Action(action));
+    }
+
+    let mut parts = input.split_whitespace();
+    let name = parts.next()?.to_lowercase();
+    let arg = parts.next()?.to_string();
+    if parts.next().is_some() {
+        return None;
+    }
+
+    match name.as_str() {
+        "theme" => Some(Command::Theme(arg)),
+        "profile" => Some(Command::Profile(arg)),
+        _ => None,
+    }
+}
+
+/// Seconds in `5`, `5m` or `30s`, or `None` if it doesn't fit an `i64`.
+fn parse_seconds(arg: &str) -> Option<i64> {
+    let (number, multiplier) = match arg.strip_suffix('s') {
+        Some(number) => (number, 1),
+        None => (arg.strip_suffix('m').unwrap_or(arg), 60),
+    };
+    number.parse::<i64>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_timer_commands() {
+        assert_eq!(parse_command("toggle"), Some(Action::TogglePause));
+        assert_eq!(parse_command("  SKIP "), Some(Action::Skip));
+        assert_eq!(parse_command("extend 5"), Some(Action::Adjust(300)));
+        assert_eq!(parse_command("extend 5m"), Some(Action::Adjust(300)));
+        assert_eq!(parse_command("shorten 30s"), Some(Action::Adjust(-30)));
+        assert_eq!(parse_command("idle"), Some(Action::Idle(None)));
+        assert_eq!(parse_command("idle 90s"), Some(Action::Idle(Some(90))));
+        assert_eq!(parse_command("emergency-skip"), Some(Action::EmergencySkip));
+    }
+
+    #[test]
+    fn palette_commands_parse() {
+        for (command, usage) in COMMANDS {
+            let input = match (*command, usage) {
+                (_, None) => command.to_string(),
+                ("extend" | "shorten", _) => format!("{} 1", command),
+                (_, Some(_)) => format!("{} nord", command),
+            };
+            assert!(parse(&input).is_some(), "`{}` should parse", input);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(parse_command(""), None);
+        assert_eq!(parse_command("extend"), None);
+        assert_eq!(parse_command("extend five"), None);
+        assert_eq!(parse_command("extend 5h"), None);
+        assert_eq!(parse_command("skip now"), None);
+        assert_eq!(parse_command("extend 1 2"), None);
+        assert_eq!(parse_command("idle -5"), None);
+        assert_eq!(parse_command("frobnicate"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_numbers() {
+        assert_eq!(parse_command("extend 153722867280912931"), None);
+        assert_eq!(parse_command("extend 99999999999999999999"), None);
+        assert_eq!(parse_command("shorten -9223372036854775808s"), None);
+        assert_eq!(parse_command("shorten 9223372036854775807s"), Some(Action::Adjust(-i64::MAX)));
+    }
+}
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
@@ -7,18 +7,42 @@ pub enum Action {
     Reset,
     Skip,
     ToggleHelp,
+    CancelAutoStart,
+    /// Lengthen (positive) or shorten (negative) the current phase, in seconds
+    Adjust(i64),
+    /// The user has been idle for this many seconds, or `None` for as long
+    /// as the idle threshold
+    Idle(Option<u64>),
+    /// The user is back
+    Active,
+    /// Spend one of today's emergency skips to end a strict break
+    EmergencySkip,
+    /// Push a strict break back by the configured number of minutes
+    PostponeBreak,
+    /// Show where the progress bar was clicked, as a percentage; `None`
+    /// hides the preview. The timer itself is left alone.
+    SeekPreview(Option<u16>),
+    /// Scroll the help popup by this many lines
+    ScrollHelp(i16),
+    /// Open the `:` command palette
+    OpenPalette,
     None,
 }
 
-pub fn handle_key(key: KeyEvent) -> Action {
+/// Keys while the help popup is open: the arrows, j/k and the paging keys
+/// scroll it, anything else closes it.
+pub fn help_key(key: KeyEvent) -> Action {
+    if key.kind != KeyEventKind::Press {
+        return Action::None;
+    }
+
     match key.code {
-        KeyCode::Char('q') | KeyCode::Char('Q') => Action::Quit,
-        KeyCode::Esc => Action::Quit,
-        KeyCode::Char(' ') | KeyCode::Char('p') | KeyCode::Char('P') => Action::TogglePause,
-        KeyCode::Char('r') | KeyCode::Char('R') => Action::Reset,
-        KeyCode::Char('s') | KeyCode::Char('S') => Action::Skip,
-        KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Char('?') => Action::ToggleHelp,
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-        _ => Action::None,
+        KeyCode::Up | KeyCode::Char('k') => Action::ScrollHelp(-1),
+        KeyCode::Down | KeyCode::Char('j') => Action::ScrollHelp(1),
+        KeyCode::PageUp => Action::ScrollHelp(-10),
+        KeyCode::PageDown => Action::ScrollHelp(10),
+        KeyCode::Home | KeyCode::Char('g') => Action::ScrollHelp(i16::MIN),
+        KeyCode::End | KeyCode::Char('G') => Action::ScrollHelp(i16::MAX),
+        _ => Action::ToggleHelp,
     }
 }
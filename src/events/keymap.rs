@@ -0,0 +1,332 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crate::config::settings::KeySpec;
+use crate::error::{Error, Result};
+use super::command::parse_command;
+use super::keyboard::Action;
+
+/// Built-in bindings, in the order the help screen lists them. Commands use
+/// the same syntax as `devchron ctl`.
+const DEFAULT_BINDINGS: &[(&str, &[&str])] = &[
+    ("toggle", &["space", "p"]),
+    ("reset", &["r"]),
+    ("skip", &["s"]),
+    ("cancel", &["c"]),
+    ("extend 1", &["+"]),
+    ("shorten 1", &["-"]),
+    ("extend 5", &["]"]),
+    ("shorten 5", &["["]),
+    ("emergency-skip", &["e"]),
+    ("postpone", &["z"]),
+    ("palette", &[":"]),
+    ("help", &["h", "?"]),
+    ("quit", &["q", "esc", "ctrl-c"]),
+];
+
+/// A key plus modifiers, e.g. `ctrl-s`. Shift is folded into the
+/// character, so `shift-a` and `A` are the same binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = input;
+
+        // Peel off modifiers, leaving `-` itself usable as a key
+        loop {
+            let lower = rest.to_lowercase();
+            let (modifier, len) = if lower.starts_with("ctrl-") {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt-") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift-") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            if rest.len() == len {
+                break;
+            }
+            modifiers |= modifier;
+            rest = &rest[len..];
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => KeyCode::F(name[1..].parse().ok()?),
+            _ => {
+                let mut chars = rest.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+        };
+
+        Some(Self::normalized(code, modifiers))
+    }
+
+    fn from_event(key: KeyEvent) -> Self {
+        Self::normalized(key.code, key.modifiers)
+    }
+
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    /// The lowercase variant of an uppercase letter, so `q` also answers
+    /// to `Q` unless `Q` has a binding of its own.
+    fn lowercase(self) -> Option<Self> {
+        match self.code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => Some(Self {
+                code: KeyCode::Char(c.to_ascii_lowercase()),
+                modifiers: self.modifiers,
+            }),
+            _ => None,
+        }
+    }
+
+    fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(c) if c != ' ') && self.modifiers.is_empty()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// The keys bound to one command.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub command: String,
+    pub action: Action,
+    pub keys: Vec<KeyBinding>,
+}
+
+/// Maps key presses to actions: the defaults with `[keybindings]` applied.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Keymap {
+    pub fn new(overrides: &BTreeMap<String, KeySpec>) -> Result<Self> {
+        let mut bindings: Vec<Binding> = DEFAULT_BINDINGS
+            .iter()
+            .map(|(command, keys)| Binding {
+                command: command.to_string(),
+                action: parse_command(command).expect("default bindings use valid commands"),
+                keys: keys.iter().filter_map(|key| KeyBinding::parse(key)).collect(),
+            })
+            .collect();
+
+        for (command, spec) in overrides {
+            let action = parse_command(command).ok_or_else(|| Error::Config(format!(
+                "[keybindings]: unknown command `{}`",
+                command
+            )))?;
+            let keys = spec
+                .keys()
+                .iter()
+                .map(|key| KeyBinding::parse(key).ok_or_else(|| Error::Config(format!(
+                    "[keybindings]: can't parse key `{}` for `{}`",
+                    key, command
+                ))))
+                .collect::<Result<Vec<_>>>()?;
+
+            // `pause = ...` replaces the default `toggle` keys, they're one action
+            match bindings.iter_mut().find(|binding| binding.action == action) {
+                Some(binding) => binding.keys = keys,
+                None => bindings.push(Binding { command: command.clone(), action, keys }),
+            }
+        }
+
+        let mut seen: HashMap<KeyBinding, &str> = HashMap::new();
+        for binding in &bindings {
+            for key in &binding.keys {
+                if let Some(other) = seen.insert(*key, &binding.command) {
+                    return Err(Error::Config(format!(
+                        "[keybindings]: `{}` is bound to both `{}` and `{}`",
+                        key, other, binding.command
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { bindings })
+    }
+
+    pub fn action(&self, key: KeyEvent) -> Action {
+        if key.kind != KeyEventKind::Press {
+            return Action::None;
+        }
+
+        let pressed = KeyBinding::from_event(key);
+        self.lookup(pressed)
+            .or_else(|| pressed.lowercase().and_then(|key| self.lookup(key)))
+            .unwrap_or(Action::None)
+    }
+
+    fn lookup(&self, key: KeyBinding) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.keys.contains(&key))
+            .map(|binding| binding.action)
+    }
+
+    /// Bindings with at least one key, in help-screen order.
+    pub fn bindings(&self) -> impl Iterator<Item = &Binding> {
+        self.bindings.iter().filter(|binding| !binding.keys.is_empty())
+    }
+
+    /// The key to show on an on-screen button: the first plain character
+    /// if there is one, since those read best as `［ｐ］`.
+    pub fn key_for(&self, action: Action) -> Option<KeyBinding> {
+        let keys = &self.bindings.iter().find(|binding| binding.action == action)?.keys;
+        keys.iter().find(|key| key.is_plain_char()).or(keys.first()).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyBinding {
+        KeyBinding::normalized(code, modifiers)
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn keymap(overrides: &[(&str, &[&str])]) -> Result<Keymap> {
+        let overrides = overrides
+            .iter()
+            .map(|(command, keys)| {
+                let keys = keys.iter().map(|k| k.to_string()).collect();
+                (command.to_string(), KeySpec::Many(keys))
+            })
+            .collect();
+        Keymap::new(&overrides)
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(KeyBinding::parse("ctrl-s"), Some(key(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert_eq!(
+            KeyBinding::parse("Ctrl-Alt-x"),
+            Some(key(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT))
+        );
+        assert_eq!(KeyBinding::parse("shift-a"), KeyBinding::parse("A"));
+        assert_eq!(KeyBinding::parse("shift-tab"), Some(key(KeyCode::BackTab, KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("space"), Some(key(KeyCode::Char(' '), KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("Esc"), Some(key(KeyCode::Esc, KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("pagedown"), Some(key(KeyCode::PageDown, KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("f5"), Some(key(KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("f"), Some(key(KeyCode::Char('f'), KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("-"), Some(key(KeyCode::Char('-'), KeyModifiers::NONE)));
+        assert_eq!(KeyBinding::parse("ctrl-"), None);
+        assert_eq!(KeyBinding::parse("alt--"), Some(key(KeyCode::Char('-'), KeyModifiers::ALT)));
+        assert_eq!(KeyBinding::parse("ctrl-s").unwrap().to_string(), "Ctrl-s");
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for input in ["", "ctrl-", "spacebar", "fx", "f999", "ab", "meta-a"] {
+            assert_eq!(KeyBinding::parse(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn defaults_answer_to_their_keys() {
+        let keymap = keymap(&[]).unwrap();
+        assert_eq!(keymap.action(press(KeyCode::Char(' '), KeyModifiers::NONE)), Action::TogglePause);
+        assert_eq!(keymap.action(press(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+        assert_eq!(keymap.action(press(KeyCode::Char('Q'), KeyModifiers::SHIFT)), Action::Quit);
+        assert_eq!(keymap.action(press(KeyCode::Char('x'), KeyModifiers::NONE)), Action::None);
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = keymap(&[("pause", &["ctrl-p"]), ("skip", &[])]).unwrap();
+        assert_eq!(keymap.action(press(KeyCode::Char('p'), KeyModifiers::CONTROL)), Action::TogglePause);
+        assert_eq!(keymap.action(press(KeyCode::Char(' '), KeyModifiers::NONE)), Action::None);
+        assert_eq!(keymap.action(press(KeyCode::Char('p'), KeyModifiers::NONE)), Action::None);
+        assert_eq!(keymap.action(press(KeyCode::Char('s'), KeyModifiers::NONE)), Action::None);
+        assert_eq!(keymap.key_for(Action::TogglePause), KeyBinding::parse("ctrl-p"));
+        assert!(keymap.bindings().all(|binding| binding.action != Action::Skip));
+    }
+
+    #[test]
+    fn rejects_bad_overrides() {
+        let message = |result: Result<Keymap>| match result {
+            Err(Error::Config(message)) => message,
+            other => panic!("expected a config error, got {:?}", other.map(|_| ())),
+        };
+        assert!(message(keymap(&[("dance", &["d"])])).contains("unknown command `dance`"));
+        assert!(message(keymap(&[("skip", &["ctrl-nope"])])).contains("can't parse key `ctrl-nope`"));
+        assert!(message(keymap(&[("skip", &["q"])])).contains("`q` is bound to both"));
+        assert!(message(keymap(&[("skip", &["x", "shift-x"]), ("reset", &["X"])])).contains("`X` is bound to both"));
+    }
+}
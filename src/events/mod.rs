@@ -1,3 +1,11 @@
 pub mod keyboard;
+pub mod command;
+pub mod mouse;
+pub mod keymap;
+pub mod palette;
 
-pub use keyboard::{handle_key, Action};
+pub use keyboard::{help_key, Action};
+pub use keymap::Keymap;
+pub use palette::{Palette, PaletteEvent};
+pub use command::{parse, parse_command, Command};
+pub use mouse::handle_mouse;
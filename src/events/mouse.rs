@@ -0,0 +1,35 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crate::ui::hitbox::{Hitboxes, Target};
+use super::Action;
+
+/// Seconds added or removed per scroll step over the timer.
+const SCROLL_STEP_SECS: i64 = 60;
+
+/// Lines moved per scroll step over the help popup.
+const HELP_SCROLL_LINES: i16 = 3;
+
+pub fn handle_mouse(event: MouseEvent, hitboxes: &Hitboxes) -> Action {
+    let hit = hitboxes.at(event.column, event.row);
+
+    match (event.kind, hit) {
+        (MouseEventKind::Down(MouseButton::Left), Some((_, Target::Button(action)))) => action,
+        (MouseEventKind::ScrollUp, Some((_, Target::Timer))) => Action::Adjust(SCROLL_STEP_SECS),
+        (MouseEventKind::ScrollDown, Some((_, Target::Timer))) => Action::Adjust(-SCROLL_STEP_SECS),
+        (MouseEventKind::ScrollUp, Some((_, Target::Help))) => Action::ScrollHelp(-HELP_SCROLL_LINES),
+        (MouseEventKind::ScrollDown, Some((_, Target::Help))) => Action::ScrollHelp(HELP_SCROLL_LINES),
+        (MouseEventKind::Down(MouseButton::Left), Some((_, Target::Help))) => Action::ToggleHelp,
+        (
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left),
+            Some((area, Target::ProgressBar)),
+        ) => {
+            let offset = event.column.saturating_sub(area.x) as u32;
+            let span = area.width.saturating_sub(1).max(1) as u32;
+            Action::SeekPreview(Some((offset * 100 / span).min(100) as u16))
+        }
+        // Letting go anywhere, or dragging off the bar, ends the preview
+        (MouseEventKind::Up(MouseButton::Left), _) | (MouseEventKind::Drag(MouseButton::Left), _) => {
+            Action::SeekPreview(None)
+        }
+        _ => Action::None,
+    }
+}
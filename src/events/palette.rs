@@ -0,0 +1,355 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use super::command::COMMANDS;
+
+/// Commands kept for Up/Down recall.
+const HISTORY_LIMIT: usize = 100;
+
+/// What a key press in the palette asks of the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteEvent {
+    None,
+    Close,
+    /// Enter was pressed on this input
+    Run(String),
+}
+
+/// A completion for the current input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The input Tab fills in
+    pub text: String,
+    /// The command it belongs to, for looking up a description
+    pub command: &'static str,
+    /// Placeholder for an argument still to be typed
+    pub usage: Option<&'static str>,
+}
+
+/// The `:` command line: input, completion over `COMMANDS` and their
+/// arguments, and history.
+#[derive(Debug, Default)]
+pub struct Palette {
+    open: bool,
+    pub input: String,
+    /// Shown until the next edit, when a command fails
+    pub error: Option<String>,
+    /// Argument values per command, gathered when the palette opens
+    choices: Vec<(&'static str, Vec<String>)>,
+    /// Input Tab started from and the suggestion it's on
+    completion: Option<(String, usize)>,
+    history: Vec<String>,
+    /// Position while walking the history, and the input from before
+    browsing: Option<(usize, String)>,
+}
+
+impl Palette {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self, choices: Vec<(&'static str, Vec<String>)>) {
+        self.open = true;
+        self.choices = choices;
+        self.input.clear();
+        self.error = None;
+        self.completion = None;
+        self.browsing = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Closes the palette after a command ran, remembering it.
+    pub fn finish(&mut self, line: &str) {
+        let line = line.trim();
+        if !line.is_empty() && self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+            if self.history.len() > HISTORY_LIMIT {
+                self.history.remove(0);
+            }
+        }
+        self.close();
+    }
+
+    /// Keeps the palette open with the input, showing why it failed.
+    pub fn fail(&mut self, message: String) {
+        self.error = Some(message);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteEvent {
+        if key.kind != KeyEventKind::Press {
+            return PaletteEvent::None;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PaletteEvent::Close,
+            KeyCode::Char('c') if ctrl => return PaletteEvent::Close,
+            KeyCode::Enter => return PaletteEvent::Run(self.input.clone()),
+            KeyCode::Tab => return self.complete(1),
+            KeyCode::BackTab => return self.complete(-1),
+            KeyCode::Up => return self.recall(true),
+            KeyCode::Down => return self.recall(false),
+            // Backspace on an empty line backs out of the palette, like vim
+            KeyCode::Backspace if self.input.is_empty() => return PaletteEvent::Close,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char('u') if ctrl => self.input.clear(),
+            KeyCode::Char('w') if ctrl => {
+                let trimmed = self.input.trim_end();
+                let word_start = trimmed.rfind(' ').map_or(0, |i| i + 1);
+                self.input.truncate(word_start);
+            }
+            KeyCode::Char(c) if !ctrl => self.input.push(c),
+            _ => return PaletteEvent::None,
+        }
+
+        self.edited();
+        PaletteEvent::None
+    }
+
+    fn edited(&mut self) {
+        self.error = None;
+        self.completion = None;
+        self.browsing = None;
+    }
+
+    /// Steps through the suggestions for the input Tab started from.
+    fn complete(&mut self, step: isize) -> PaletteEvent {
+        let (base, index) = match &self.completion {
+            Some((base, index)) => {
+                let count = self.suggestions_for(base).len() as isize;
+                (base.clone(), (*index as isize + step).rem_euclid(count.max(1)) as usize)
+            }
+            None if step < 0 => {
+                let count = self.suggestions_for(&self.input).len();
+                (self.input.clone(), count.saturating_sub(1))
+            }
+            None => (self.input.clone(), 0),
+        };
+
+        let suggestions = self.suggestions_for(&base);
+        if let Some(suggestion) = suggestions.get(index) {
+            self.input = suggestion.text.clone();
+            self.error = None;
+            // With nothing to cycle through, the next Tab completes the
+            // argument instead
+            self.completion = (suggestions.len() > 1).then_some((base, index));
+        }
+        PaletteEvent::None
+    }
+
+    fn recall(&mut self, older: bool) -> PaletteEvent {
+        if self.history.is_empty() {
+            return PaletteEvent::None;
+        }
+
+        let position = match (&self.browsing, older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => return PaletteEvent::None,
+            (Some((position, _)), true) => Some(position.saturating_sub(1)),
+            (Some((position, _)), false) if position + 1 < self.history.len() => Some(position + 1),
+            (Some(_), false) => None,
+        };
+
+        let draft = match self.browsing.take() {
+            Some((_, draft)) => draft,
+            None => self.input.clone(),
+        };
+        match position {
+            Some(position) => {
+                self.input = self.history[position].clone();
+                self.browsing = Some((position, draft));
+            }
+            // Past the newest entry: back to what was being typed
+            None => self.input = draft,
+        }
+        self.error = None;
+        self.completion = None;
+        PaletteEvent::None
+    }
+
+    /// Suggestions for what's on screen, and which one Tab is on.
+    pub fn suggestions(&self) -> (Vec<Suggestion>, Option<usize>) {
+        match &self.completion {
+            Some((base, index)) => (self.suggestions_for(base), Some(*index)),
+            None => (self.suggestions_for(&self.input), None),
+        }
+    }
+
+    fn suggestions_for(&self, input: &str) -> Vec<Suggestion> {
+        let input = input.trim_start();
+        let Some((name, arg)) = input.split_once(' ') else {
+            // Still typing the command name
+            return ranked(input, COMMANDS.iter().map(|(name, usage)| (*name, *usage)), |name, usage| {
+                Suggestion {
+                    text: if usage.is_some() { format!("{} ", name) } else { name.to_string() },
+                    command: name,
+                    usage,
+                }
+            });
+        };
+
+        let Some(&(command, usage)) = COMMANDS.iter().find(|(command, _)| command.eq_ignore_ascii_case(name)) else {
+            return Vec::new();
+        };
+        let arg = arg.trim_start();
+        match self.choices.iter().find(|(name, _)| *name == command) {
+            Some((_, values)) => ranked(arg, values.iter().map(|value| (value.as_str(), usage)), |value, _| {
+                Suggestion { text: format!("{} {}", command, value), command, usage: None }
+            }),
+            // Free-form argument: just say what goes there
+            None if arg.is_empty() => usage
+                .map(|usage| vec![Suggestion { text: input.to_string(), command, usage: Some(usage) }])
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Candidates matching `pattern`, best first; ties keep their order.
+fn ranked<'a, T>(
+    pattern: &str,
+    candidates: impl Iterator<Item = (&'a str, T)>,
+    suggestion: impl Fn(&'a str, T) -> Suggestion,
+) -> Vec<Suggestion> {
+    let mut scored: Vec<(i32, Suggestion)> = candidates
+        .filter_map(|(candidate, extra)| {
+            fuzzy_score(pattern, candidate).map(|score| (score, suggestion(candidate, extra)))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, suggestion)| suggestion).collect()
+}
+
+/// How well `pattern` matches `candidate` as a case-insensitive
+/// subsequence, or `None` if it doesn't. Consecutive characters and word
+/// starts count for more and skipped characters for less, so `es` puts
+/// `emergency-skip` ahead of `reset`.
+fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i32> {
+    let mut pattern = pattern.chars().map(|c| c.to_ascii_lowercase()).peekable();
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous: Option<char> = None;
+
+    for c in candidate.chars() {
+        let Some(&wanted) = pattern.peek() else {
+            break;
+        };
+        if c.to_ascii_lowercase() == wanted {
+            pattern.next();
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if matches!(previous, None | Some('-' | ' ' | '_')) {
+                score += 8;
+            }
+            previous_matched = true;
+        } else {
+            score -= 1;
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    pattern.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(palette: &mut Palette, code: KeyCode) -> PaletteEvent {
+        palette.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(palette: &mut Palette, text: &str) {
+        for c in text.chars() {
+            press(palette, KeyCode::Char(c));
+        }
+    }
+
+    fn texts(palette: &Palette) -> Vec<String> {
+        palette.suggestions().0.into_iter().map(|s| s.text).collect()
+    }
+
+    #[test]
+    fn prefix_beats_subsequence() {
+        assert!(fuzzy_score("sk", "skip") > fuzzy_score("sk", "emergency-skip"));
+        assert!(fuzzy_score("es", "emergency-skip") > fuzzy_score("es", "reset"));
+        assert!(fuzzy_score("RST", "reset").is_some());
+        assert_eq!(fuzzy_score("xyz", "skip"), None);
+        assert_eq!(fuzzy_score("skipp", "skip"), None);
+        assert_eq!(fuzzy_score("", "skip"), Some(0));
+    }
+
+    #[test]
+    fn ranks_best_match_first() {
+        let mut palette = Palette::default();
+        palette.open(Vec::new());
+        type_text(&mut palette, "s");
+        // Equal scores keep the order of COMMANDS
+        assert_eq!(texts(&palette)[..2], ["skip", "shorten "]);
+
+        type_text(&mut palette, "q");
+        assert!(texts(&palette).is_empty());
+    }
+
+    #[test]
+    fn tab_cycles_through_suggestions() {
+        let mut palette = Palette::default();
+        palette.open(vec![("theme", vec!["dark".to_string(), "light".to_string()])]);
+        type_text(&mut palette, "s");
+
+        press(&mut palette, KeyCode::Tab);
+        assert_eq!(palette.input, "skip");
+        press(&mut palette, KeyCode::Tab);
+        assert_eq!(palette.input, "shorten ");
+        press(&mut palette, KeyCode::BackTab);
+        press(&mut palette, KeyCode::BackTab);
+        assert_eq!(palette.input, texts(&palette).last().unwrap().as_str());
+
+        palette.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        type_text(&mut palette, "theme l");
+        press(&mut palette, KeyCode::Tab);
+        assert_eq!(palette.input, "theme light");
+        assert_eq!(press(&mut palette, KeyCode::Enter), PaletteEvent::Run("theme light".to_string()));
+    }
+
+    #[test]
+    fn history_recalls_newest_first_without_repeats() {
+        let mut palette = Palette::default();
+        for line in ["skip", "extend 5", "extend 5", "  ", "reset"] {
+            palette.open(Vec::new());
+            palette.finish(line);
+        }
+        assert_eq!(palette.history, ["skip", "extend 5", "reset"]);
+
+        palette.open(Vec::new());
+        type_text(&mut palette, "to");
+        let mut recalled = Vec::new();
+        for _ in 0..4 {
+            press(&mut palette, KeyCode::Up);
+            recalled.push(palette.input.clone());
+        }
+        assert_eq!(recalled, ["reset", "extend 5", "skip", "skip"]);
+
+        press(&mut palette, KeyCode::Down);
+        press(&mut palette, KeyCode::Down);
+        assert_eq!(palette.input, "reset");
+        press(&mut palette, KeyCode::Down);
+        assert_eq!(palette.input, "to");
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut palette = Palette::default();
+        for i in 0..HISTORY_LIMIT + 5 {
+            palette.finish(&format!("extend {}", i));
+        }
+        assert_eq!(palette.history.len(), HISTORY_LIMIT);
+        assert_eq!(palette.history[0], "extend 5");
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use chrono::{DateTime, Local};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use crate::log;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HistoryEvent {
+    /// The current phase was lengthened or shortened by `seconds`
+    Adjust { phase: String, seconds: i64 },
+    /// Window usage for a finished focus session, seconds per class
+    FocusActivity {
+        score: u8,
+        distracted_seconds: u64,
+        windows: BTreeMap<String, u64>,
+    },
+    /// A strict break was skipped using the daily emergency allowance
+    EmergencySkip,
+    /// A strict break was pushed back by `minutes`
+    PostponeBreak { minutes: u64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+}
+
+impl HistoryEntry {
+    pub fn is_today(&self) -> bool {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|t| t.with_timezone(&Local).date_naive() == Local::now().date_naive())
+            .unwrap_or(false)
+    }
+}
+
+/// Append-only log of notable timer events, one JSON object per line in
+/// `~/.local/share/devchron/history.jsonl`.
+pub struct History {
+    path: Option<PathBuf>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            path: Self::get_history_path(),
+        }
+    }
+
+    fn get_history_path() -> Option<PathBuf> {
+        let proj_dirs = ProjectDirs::from("", "", "devchron")?;
+        let data_dir = proj_dirs.data_dir();
+        fs::create_dir_all(data_dir).ok()?;
+        Some(data_dir.join("history.jsonl"))
+    }
+
+    pub fn record(&self, event: HistoryEvent) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let entry = HistoryEntry {
+            timestamp: Local::now().to_rfc3339(),
+            event,
+        };
+
+        if let Ok(json) = serde_json::to_string(&entry) {
+            match OpenOptions::new().create(true).append(true).open(path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", json);
+                }
+                Err(e) => log::error!("Failed to write history: {}", e),
+            }
+        }
+    }
+
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        let Some(file) = self.path.as_ref().and_then(|p| fs::File::open(p).ok()) else {
+            return Vec::new();
+        };
+
+        BufReader::new(file)
+            .lines()
+            .map_while(|line| line.ok())
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,280 @@
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::Local;
+use directories::ProjectDirs;
+use tokio::process::Command;
+use crate::config::settings::HookSettings;
+use crate::timer::{PomodoroTimer, TimerPhase};
+use crate::log::{self, LogFile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    FocusStart,
+    FocusEnd,
+    BreakStart,
+    BreakEnd,
+    Pause,
+    Resume,
+    Skip,
+}
+
+impl HookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::FocusStart => "on_focus_start",
+            HookEvent::FocusEnd => "on_focus_end",
+            HookEvent::BreakStart => "on_break_start",
+            HookEvent::BreakEnd => "on_break_end",
+            HookEvent::Pause => "on_pause",
+            HookEvent::Resume => "on_resume",
+            HookEvent::Skip => "on_skip",
+        }
+    }
+
+    pub fn phase_start(phase: TimerPhase) -> Self {
+        match phase {
+            TimerPhase::Focus => HookEvent::FocusStart,
+            TimerPhase::ShortBreak | TimerPhase::LongBreak => HookEvent::BreakStart,
+        }
+    }
+
+    pub fn phase_end(phase: TimerPhase) -> Self {
+        match phase {
+            TimerPhase::Focus => HookEvent::FocusEnd,
+            TimerPhase::ShortBreak | TimerPhase::LongBreak => HookEvent::BreakEnd,
+        }
+    }
+}
+
+/// The timer as a hook sees it. Taken before the timer moves on, so the
+/// hooks for a phase that just ended still describe that phase.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    phase: TimerPhase,
+    remaining: Duration,
+    time: String,
+    session: String,
+    /// What's being worked on. devchron has no task list yet, so this is
+    /// empty, but hooks can already pass it along
+    task: String,
+}
+
+impl HookContext {
+    pub fn new(timer: &PomodoroTimer) -> Self {
+        Self {
+            phase: timer.current_phase(),
+            remaining: timer.current_timer.remaining,
+            time: timer.current_timer.format_time(),
+            session: timer.session_info(),
+            task: String::new(),
+        }
+    }
+}
+
+/// Runs user-configured shell commands when timer events happen.
+///
+/// Commands run through `sh -c` in the background so a slow hook never
+/// stalls the UI. Output and exit status are appended to `hooks.log`,
+/// which is rotated like the main log.
+pub struct HookRunner {
+    settings: HookSettings,
+    log: Option<Arc<Mutex<LogFile>>>,
+}
+
+impl HookRunner {
+    pub fn new(settings: HookSettings) -> Self {
+        Self {
+            settings,
+            log: Self::open_log(),
+        }
+    }
+
+    fn open_log() -> Option<Arc<Mutex<LogFile>>> {
+        let proj_dirs = ProjectDirs::from("", "", "devchron")?;
+        let log_dir = proj_dirs.state_dir().unwrap_or(proj_dirs.cache_dir());
+        let log = LogFile::open(log_dir.join("hooks.log")).ok()?;
+        Some(Arc::new(Mutex::new(log)))
+    }
+
+    fn command_for(&self, event: HookEvent) -> Option<&str> {
+        let command = match event {
+            HookEvent::FocusStart => &self.settings.on_focus_start,
+            HookEvent::FocusEnd => &self.settings.on_focus_end,
+            HookEvent::BreakStart => &self.settings.on_break_start,
+            HookEvent::BreakEnd => &self.settings.on_break_end,
+            HookEvent::Pause => &self.settings.on_pause,
+            HookEvent::Resume => &self.settings.on_resume,
+            HookEvent::Skip => &self.settings.on_skip,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+
+    pub fn fire(&self, event: HookEvent, context: &HookContext) {
+        let Some(command) = self.command_for(event) else {
+            return;
+        };
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(command)
+            .env("DEVCHRON_EVENT", event.name())
+            .env("DEVCHRON_PHASE", context.phase.as_str())
+            .env("DEVCHRON_REMAINING", context.remaining.as_secs().to_string())
+            .env("DEVCHRON_TIME", &context.time)
+            .env("DEVCHRON_SESSION", &context.session)
+            .env("DEVCHRON_TASK", &context.task)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // Its own process group, so a timeout can take down whatever
+            // the hook started along with `sh`
+            .process_group(0)
+            .kill_on_drop(true);
+
+        let command = command.to_string();
+        let timeout = Duration::from_secs(self.settings.timeout_secs);
+        let hook_log = self.log.clone();
+
+        tokio::spawn(async move {
+            let (outcome, failure) = run(cmd, timeout).await;
+
+            match failure {
+                Some(reason) => log::warning!("Hook {} failed: {}", event.name(), reason),
+                None => log::debug!("Hook {} finished: {}", event.name(), command),
+            }
+
+            if let Some(hook_log) = hook_log {
+                write_log(&hook_log, event, &command, &outcome);
+            }
+        });
+    }
+}
+
+/// Run a hook until it exits or `timeout` passes. Returns what to log and,
+/// if it failed, why.
+async fn run(mut cmd: Command, timeout: Duration) -> (String, Option<String>) {
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return (format!("failed to spawn: {}\n", e), Some(e.to_string())),
+    };
+    let group = child.id();
+
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => {
+            let mut text = format!("exit: {}\n", output.status);
+            text.push_str(&String::from_utf8_lossy(&output.stdout));
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            let failure = (!output.status.success()).then(|| output.status.to_string());
+            (text, failure)
+        }
+        Ok(Err(e)) => (format!("failed: {}\n", e), Some(e.to_string())),
+        // Dropping the child on timeout kills `sh`, and anything it started
+        // is in the same group
+        Err(_) => {
+            if let Some(group) = group {
+                kill_group(group);
+            }
+            let reason = format!("timed out after {}s", timeout.as_secs());
+            (format!("{}\n", reason), Some(reason))
+        }
+    }
+}
+
+fn kill_group(group: u32) {
+    // SAFETY: kill(2) takes no pointers; a negative pid names the group
+    // the hook was started in
+    unsafe {
+        libc::kill(-(group as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+fn write_log(hook_log: &Mutex<LogFile>, event: HookEvent, command: &str, outcome: &str) {
+    let mut entry = format!(
+        "[{}] {} `{}`\n{}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        event.name(),
+        command,
+        outcome
+    );
+
+    if !entry.ends_with('\n') {
+        entry.push('\n');
+    }
+
+    let _ = hook_log.lock().unwrap_or_else(|e| e.into_inner()).append(&entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn runner(settings: HookSettings) -> HookRunner {
+        HookRunner { settings, log: None }
+    }
+
+    async fn wait_for_file(path: &Path) -> String {
+        for _ in 0..100 {
+            if let Ok(content) = fs::read_to_string(path) {
+                if content.ends_with('\n') {
+                    return content;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("{} was never written", path.display());
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("devchron-hooks-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn exports_the_timer_to_hooks() {
+        let dir = temp_dir("env");
+        let out = dir.join("env");
+        let runner = runner(HookSettings {
+            on_focus_start: Some(format!(
+                "echo \"$DEVCHRON_EVENT $DEVCHRON_PHASE $DEVCHRON_REMAINING ${{DEVCHRON_TASK+task:$DEVCHRON_TASK}}\" > {}",
+                out.display()
+            )),
+            ..HookSettings::default()
+        });
+
+        let timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        runner.fire(HookEvent::FocusStart, &HookContext::new(&timer));
+        assert_eq!(wait_for_file(&out).await, "on_focus_start focus 1500 task:\n");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn timeout_kills_what_the_hook_started() {
+        let dir = temp_dir("timeout");
+        let pid_file = dir.join("pid");
+        let runner = runner(HookSettings {
+            on_pause: Some(format!("sleep 30 & echo $! > {}; wait", pid_file.display())),
+            timeout_secs: 1,
+            ..HookSettings::default()
+        });
+
+        let timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        runner.fire(HookEvent::Pause, &HookContext::new(&timer));
+        let pid: i32 = wait_for_file(&pid_file).await.trim().parse().unwrap();
+
+        // SAFETY: signal 0 only checks that the process exists
+        let alive = || unsafe { libc::kill(pid, 0) } == 0;
+        for _ in 0..60 {
+            if !alive() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(!alive(), "sleep outlived the hook's timeout");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use crate::config::settings::HyprlandSettings;
+use crate::log;
+use super::HyprlandClient;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ActiveWindow {
+    pub class: String,
+    pub title: String,
+}
+
+/// Summary of one focus session's window usage.
+#[derive(Debug, Clone)]
+pub struct ActivityReport {
+    pub score: u8,
+    pub distracted: Duration,
+    pub per_class: BTreeMap<String, u64>,
+}
+
+/// First wait before reconnecting to the event socket, doubled after
+/// each failed attempt.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Follows Hyprland's `activewindow>>` events and tallies where focus
+/// time goes, flagging time spent in distracting windows.
+pub struct ActivityTracker {
+    client: HyprlandClient,
+    /// The new active window, or `None` while the event socket is down
+    events: UnboundedReceiver<Option<ActiveWindow>>,
+    sender: UnboundedSender<Option<ActiveWindow>>,
+    current: Option<ActiveWindow>,
+    distracting_classes: Vec<String>,
+    distracting_titles: Vec<String>,
+    per_class: HashMap<String, Duration>,
+    tracked: Duration,
+    distracted: Duration,
+}
+
+impl ActivityTracker {
+    /// Returns `None` when disabled or not running under Hyprland.
+    pub fn new(settings: &HyprlandSettings) -> Option<Self> {
+        if !settings.track_activity {
+            return None;
+        }
+        Some(Self::with_client(HyprlandClient::from_env()?, settings))
+    }
+
+    pub fn with_client(client: HyprlandClient, settings: &HyprlandSettings) -> Self {
+        let (tx, events) = mpsc::unbounded_channel();
+        tokio::spawn(listen(client.clone(), tx.clone()));
+
+        Self {
+            client,
+            events,
+            sender: tx,
+            current: None,
+            distracting_classes: lowercase(&settings.distracting_classes),
+            distracting_titles: lowercase(&settings.distracting_titles),
+            per_class: HashMap::new(),
+            tracked: Duration::ZERO,
+            distracted: Duration::ZERO,
+        }
+    }
+
+    /// Begin a fresh focus session.
+    pub fn start_session(&mut self) {
+        self.per_class.clear();
+        self.tracked = Duration::ZERO;
+        self.distracted = Duration::ZERO;
+
+        // Events only arrive on change, so ask for the window we start in.
+        // The answer comes back with the events on a later tick
+        query_active_window(&self.client, &self.sender);
+    }
+
+    /// Credit one second of focus time to the active window. Nothing is
+    /// credited while we can't tell which window that is.
+    pub fn tick(&mut self) {
+        self.idle();
+
+        let Some(window) = &self.current else {
+            return;
+        };
+        let second = Duration::from_secs(1);
+        *self.per_class.entry(window.class.clone()).or_default() += second;
+        self.tracked += second;
+        if self.is_distracting(window) {
+            self.distracted += second;
+        }
+    }
+
+    /// Drop queued window events without crediting any time, e.g. during
+    /// breaks, while remembering the latest active window.
+    pub fn idle(&mut self) {
+        while let Ok(window) = self.events.try_recv() {
+            self.current = window;
+        }
+    }
+
+    /// Percentage of tracked focus time spent outside distracting windows.
+    pub fn focus_score(&self) -> Option<u8> {
+        if self.tracked.is_zero() {
+            return None;
+        }
+        let focused = self.tracked - self.distracted;
+        Some((focused.as_secs() * 100 / self.tracked.as_secs()) as u8)
+    }
+
+    pub fn report(&self) -> Option<ActivityReport> {
+        Some(ActivityReport {
+            score: self.focus_score()?,
+            distracted: self.distracted,
+            per_class: self
+                .per_class
+                .iter()
+                .map(|(class, time)| (class.clone(), time.as_secs()))
+                .collect(),
+        })
+    }
+
+    fn is_distracting(&self, window: &ActiveWindow) -> bool {
+        let class = window.class.to_lowercase();
+        let title = window.title.to_lowercase();
+        self.distracting_classes.contains(&class)
+            || self.distracting_titles.iter().any(|t| title.contains(t.as_str()))
+    }
+}
+
+/// Forward active-window changes until the tracker goes away,
+/// reconnecting whenever the socket closes, e.g. when Hyprland restarts.
+async fn listen(client: HyprlandClient, tx: UnboundedSender<Option<ActiveWindow>>) {
+    let mut delay = RECONNECT_DELAY;
+    // Only the first of a run of failed attempts is worth a warning
+    let mut failing = false;
+
+    while !tx.is_closed() {
+        let stream = match UnixStream::connect(client.event_socket_path()).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                if failing {
+                    log::debug!("Hyprland: event socket still unavailable: {}", e);
+                } else {
+                    log::warning!("Hyprland: could not open event socket: {}", e);
+                }
+                failing = true;
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                continue;
+            }
+        };
+        delay = RECONNECT_DELAY;
+        failing = false;
+
+        // Events only arrive on change, so catch up on what changed while
+        // we weren't listening
+        let _ = query_active_window(&client, &tx).await;
+
+        let mut lines = BufReader::new(stream).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    if let Some(window) = parse_active_window(&line) {
+                        if tx.send(Some(window)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Ok(None) => {
+                    log::warning!("Hyprland: event socket closed, reconnecting");
+                    break;
+                }
+                Err(e) => {
+                    log::warning!("Hyprland: event socket failed, reconnecting: {}", e);
+                    break;
+                }
+            }
+        }
+
+        if tx.send(None).is_err() {
+            return;
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Send the current active window down `tx`. The request socket can
+/// take up to a second to answer, so it's asked from a blocking thread.
+fn query_active_window(client: &HyprlandClient, tx: &UnboundedSender<Option<ActiveWindow>>) -> JoinHandle<()> {
+    let (client, tx) = (client.clone(), tx.clone());
+    tokio::task::spawn_blocking(move || {
+        if let Ok(window) = client.query("j/activewindow") {
+            let _ = tx.send(Some(window));
+        }
+    })
+}
+
+/// Parse `activewindow>>class,title`. Titles may contain commas.
+fn parse_active_window(line: &str) -> Option<ActiveWindow> {
+    let data = line.strip_prefix("activewindow>>")?;
+    let (class, title) = data.split_once(',').unwrap_or((data, ""));
+    Some(ActiveWindow {
+        class: class.to_string(),
+        title: title.to_string(),
+    })
+}
+
+fn lowercase(items: &[String]) -> Vec<String> {
+    items.iter().map(|s| s.to_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    /// Answers `j/activewindow` on `.socket.sock` the way Hyprland does.
+    fn serve_active_window(dir: &Path, reply: &'static str) {
+        let listener = UnixListener::bind(dir.join(".socket.sock")).unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 64];
+                let _ = stream.read(&mut request).await;
+                let _ = stream.write_all(reply.as_bytes()).await;
+            }
+        });
+    }
+
+    async fn wait_for(tracker: &mut ActivityTracker, class: Option<&str>) {
+        for _ in 0..100 {
+            tracker.idle();
+            if tracker.current.as_ref().map(|w| w.class.as_str()) == class {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("active window never became {:?}", class);
+    }
+
+    #[tokio::test]
+    async fn session_start_asks_for_the_window_in_the_background() {
+        let dir = std::env::temp_dir().join(format!("devchron-activity-start-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        serve_active_window(&dir, r#"{"class": "firefox", "title": "docs"}"#);
+
+        // No event socket, so only the session start can report a window
+        let mut tracker =
+            ActivityTracker::with_client(HyprlandClient::with_socket_dir(dir.clone()), &HyprlandSettings::default());
+        tracker.start_session();
+        assert!(tracker.current.is_none());
+        wait_for(&mut tracker, Some("firefox")).await;
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_the_event_socket_closes() {
+        let dir = std::env::temp_dir().join(format!("devchron-activity-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        serve_active_window(&dir, r#"{"class": "kitty", "title": "vim"}"#);
+        let events = UnixListener::bind(dir.join(".socket2.sock")).unwrap();
+
+        let settings = HyprlandSettings {
+            distracting_classes: vec!["discord".to_string()],
+            ..HyprlandSettings::default()
+        };
+        let mut tracker = ActivityTracker::with_client(HyprlandClient::with_socket_dir(dir.clone()), &settings);
+
+        let (mut stream, _) = events.accept().await.unwrap();
+        wait_for(&mut tracker, Some("kitty")).await;
+        stream.write_all(b"workspace>>2\nactivewindow>>discord,general, chat\n").await.unwrap();
+        wait_for(&mut tracker, Some("discord")).await;
+        assert_eq!(tracker.current.as_ref().unwrap().title, "general, chat");
+
+        // Hyprland goes away: nothing is credited until it's back
+        drop(stream);
+        wait_for(&mut tracker, None).await;
+        tracker.tick();
+        assert_eq!(tracker.focus_score(), None);
+
+        let (mut stream, _) = events.accept().await.unwrap();
+        wait_for(&mut tracker, Some("kitty")).await;
+        stream.write_all(b"activewindow>>discord,voice\n").await.unwrap();
+        wait_for(&mut tracker, Some("discord")).await;
+        tracker.tick();
+        assert_eq!(tracker.focus_score(), Some(0));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
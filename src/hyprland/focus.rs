@@ -0,0 +1,388 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use serde::Deserialize;
+use crate::config::settings::{BlocklistAction, HyprlandSettings};
+use crate::timer::TimerPhase;
+use crate::ui::theme::{to_hex, Theme};
+use crate::log;
+use super::{Client, HyprlandClient};
+
+/// Special workspace that hidden windows are parked on during focus.
+const HIDE_WORKSPACE: &str = "special:devchron";
+
+const BORDER_OPTION: &str = "general:col.active_border";
+
+enum Request {
+    StartFocus,
+    EndFocus,
+    Border(TimerPhase),
+}
+
+/// Rearranges Hyprland for focus phases and puts things back afterwards.
+///
+/// Requests are handed to a worker thread, since each socket round trip
+/// can take up to a second. Dropping the enforcer undoes whatever it
+/// changed before returning.
+pub struct FocusEnforcer {
+    requests: Option<Sender<Request>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl FocusEnforcer {
+    /// Returns `None` when disabled or not running under Hyprland.
+    pub fn new(settings: &HyprlandSettings, theme: &Theme) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        Some(Self::with_client(HyprlandClient::from_env()?, settings, theme))
+    }
+
+    pub fn with_client(client: HyprlandClient, settings: &HyprlandSettings, theme: &Theme) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let worker = Worker {
+            client,
+            settings: settings.clone(),
+            theme: *theme,
+            previous_workspace: None,
+            focus_active: false,
+            changed: Vec::new(),
+            border_changed: false,
+            original_border: None,
+        };
+        let worker = thread::Builder::new()
+            .name("hyprland-focus".to_string())
+            .spawn(move || worker.run(rx));
+
+        match worker {
+            Ok(worker) => Self { requests: Some(tx), worker: Some(worker) },
+            Err(e) => {
+                log::warning!("Hyprland: could not start focus mode: {}", e);
+                Self { requests: None, worker: None }
+            }
+        }
+    }
+
+    pub fn start_focus(&self) {
+        self.send(Request::StartFocus);
+    }
+
+    pub fn end_focus(&self) {
+        self.send(Request::EndFocus);
+    }
+
+    pub fn set_border(&self, phase: TimerPhase) {
+        self.send(Request::Border(phase));
+    }
+
+    fn send(&self, request: Request) {
+        if let Some(requests) = &self.requests {
+            let _ = requests.send(request);
+        }
+    }
+}
+
+impl Drop for FocusEnforcer {
+    fn drop(&mut self) {
+        // Closing the channel tells the worker to put everything back.
+        // Wait for it, so windows aren't left dimmed or hidden after
+        // quitting mid-focus
+        self.requests = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Does the socket work for `FocusEnforcer`, and remembers what it
+/// changed so it can be undone.
+struct Worker {
+    client: HyprlandClient,
+    settings: HyprlandSettings,
+    theme: Theme,
+    /// Workspace we switched away from, to return to on break
+    previous_workspace: Option<String>,
+    focus_active: bool,
+    /// Addresses of the blocklisted windows dimmed or hidden this focus
+    /// phase, with the workspace each one was on
+    changed: Vec<(String, String)>,
+    border_changed: bool,
+    /// `col.active_border` from before we first changed it
+    original_border: Option<String>,
+}
+
+impl Worker {
+    fn run(mut self, requests: Receiver<Request>) {
+        for request in requests {
+            match request {
+                Request::StartFocus => self.start_focus(),
+                Request::EndFocus => self.end_focus(),
+                Request::Border(phase) => self.set_border(phase),
+            }
+        }
+
+        self.end_focus();
+        self.restore_border();
+    }
+
+    fn start_focus(&mut self) {
+        self.set_border(TimerPhase::Focus);
+        if self.focus_active {
+            return;
+        }
+        self.focus_active = true;
+
+        if let Some(workspace) = &self.settings.focus_workspace {
+            match self.client.active_workspace() {
+                Ok(current) if current != *workspace => {
+                    self.previous_workspace = Some(current);
+                    self.report(self.client.dispatch(&format!("workspace {}", workspace)));
+                }
+                Ok(_) => {}
+                Err(e) => log::warning!("Hyprland: could not read active workspace: {}", e),
+            }
+        }
+
+        if self.settings.blocklist.is_empty() {
+            return;
+        }
+        let windows = match self.client.clients() {
+            Ok(windows) => windows,
+            Err(e) => {
+                log::warning!("Hyprland: could not list windows: {}", e);
+                return;
+            }
+        };
+
+        let blocked: Vec<Client> = windows
+            .into_iter()
+            .filter(|window| self.is_blocked(window) && window.workspace.name != HIDE_WORKSPACE)
+            .collect();
+        let requests: Vec<String> = blocked
+            .iter()
+            .map(|window| match self.settings.blocklist_action {
+                BlocklistAction::Dim => format!(
+                    "setprop address:{} alpha {}",
+                    window.address, self.settings.dim_opacity
+                ),
+                BlocklistAction::Hide => format!(
+                    "dispatch movetoworkspacesilent {},address:{}",
+                    HIDE_WORKSPACE, window.address
+                ),
+            })
+            .collect();
+        self.changed = blocked
+            .into_iter()
+            .map(|window| (window.address, window.workspace.selector()))
+            .collect();
+        self.report(self.client.batch(&requests));
+    }
+
+    fn end_focus(&mut self) {
+        if !self.focus_active {
+            return;
+        }
+        self.focus_active = false;
+
+        if let Some(workspace) = self.previous_workspace.take() {
+            self.report(self.client.dispatch(&format!("workspace {}", workspace)));
+        }
+
+        // Each window goes back where it came from. Ones closed in the
+        // meantime just fail their part of the batch
+        let requests: Vec<String> = self
+            .changed
+            .drain(..)
+            .map(|(address, workspace)| match self.settings.blocklist_action {
+                BlocklistAction::Dim => format!("setprop address:{} alpha 1.0", address),
+                BlocklistAction::Hide => format!(
+                    "dispatch movetoworkspacesilent {},address:{}",
+                    workspace, address
+                ),
+            })
+            .collect();
+        self.report(self.client.batch(&requests));
+    }
+
+    fn set_border(&mut self, phase: TimerPhase) {
+        if !self.settings.phase_borders {
+            return;
+        }
+        if !self.border_changed {
+            self.border_changed = true;
+            match self.client.query::<BorderOption>(&format!("j/getoption {}", BORDER_OPTION)) {
+                Ok(option) => self.original_border = option.keyword_value(),
+                Err(e) => log::warning!("Hyprland: could not read {}: {}", BORDER_OPTION, e),
+            }
+        }
+
+        let color = match phase {
+            TimerPhase::Focus => self.theme.focus_color,
+            TimerPhase::ShortBreak => self.theme.short_break_color,
+            TimerPhase::LongBreak => self.theme.long_break_color,
+        };
+        let request = format!(
+            "keyword {} rgb({})",
+            BORDER_OPTION,
+            to_hex(color).trim_start_matches('#')
+        );
+        self.report(self.client.command(&request).map(|_| ()));
+    }
+
+    fn restore_border(&mut self) {
+        if let Some(border) = self.original_border.take() {
+            let request = format!("keyword {} {}", BORDER_OPTION, border);
+            self.report(self.client.command(&request).map(|_| ()));
+        }
+    }
+
+    fn is_blocked(&self, window: &Client) -> bool {
+        self.settings
+            .blocklist
+            .iter()
+            .any(|class| class.eq_ignore_ascii_case(&window.class))
+    }
+
+    fn report(&self, result: std::io::Result<()>) {
+        if let Err(e) = result {
+            log::warning!("Hyprland request failed: {}", e);
+        }
+    }
+}
+
+/// Reply to `j/getoption` for a gradient option.
+#[derive(Debug, Deserialize)]
+struct BorderOption {
+    custom: Option<String>,
+}
+
+impl BorderOption {
+    /// The value in the form `keyword` takes: `ee33ccff 45deg` becomes
+    /// `0xee33ccff 45deg`.
+    fn keyword_value(&self) -> Option<String> {
+        let custom = self.custom.as_deref()?.trim();
+        if custom.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = custom
+            .split_whitespace()
+            .map(|part| {
+                if part.len() == 8 && part.chars().all(|c| c.is_ascii_hexdigit()) {
+                    format!("0x{}", part)
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect();
+        Some(parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    const CLIENTS: &str = r#"[
+        {"address": "0xa", "class": "discord", "workspace": {"id": 2, "name": "2"}},
+        {"address": "0xb", "class": "Slack", "workspace": {"id": 3, "name": "chat"}},
+        {"address": "0xc", "class": "kitty", "workspace": {"id": 1, "name": "1"}},
+        {"address": "0xd", "class": "discord", "workspace": {"id": 4, "name": "4"}}
+    ]"#;
+
+    /// A `.socket.sock` in a temp dir that records requests and answers
+    /// like Hyprland would.
+    fn fake_hyprland(name: &str) -> (HyprlandClient, Arc<Mutex<Vec<String>>>) {
+        let dir = std::env::temp_dir().join(format!("devchron-hypr-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let listener = UnixListener::bind(dir.join(".socket.sock")).unwrap();
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = vec![0; 64 * 1024];
+                let len = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..len]).to_string();
+                let reply = match request.as_str() {
+                    "j/clients" => CLIENTS.to_string(),
+                    "j/activeworkspace" => r#"{"id": 1, "name": "1"}"#.to_string(),
+                    "j/getoption general:col.active_border" => {
+                        r#"{"option": "general:col.active_border", "custom": "ee33ccff ee00ff99 45deg", "set": true}"#
+                            .to_string()
+                    }
+                    batch if batch.starts_with("[[BATCH]]") => "ok\n\n".repeat(batch.split(';').count()),
+                    _ => "ok".to_string(),
+                };
+                recorded.lock().unwrap().push(request);
+                let _ = stream.write_all(reply.as_bytes());
+            }
+        });
+
+        (HyprlandClient::with_socket_dir(dir), requests)
+    }
+
+    fn settings(action: BlocklistAction, phase_borders: bool) -> HyprlandSettings {
+        HyprlandSettings {
+            enabled: true,
+            blocklist: vec!["discord".to_string(), "slack".to_string()],
+            blocklist_action: action,
+            phase_borders,
+            ..HyprlandSettings::default()
+        }
+    }
+
+    #[test]
+    fn hidden_windows_go_back_to_their_own_workspaces() {
+        let (client, requests) = fake_hyprland("hide");
+        let enforcer = FocusEnforcer::with_client(client, &settings(BlocklistAction::Hide, false), &Theme::nord());
+        enforcer.start_focus();
+        enforcer.end_focus();
+        drop(enforcer);
+
+        assert_eq!(*requests.lock().unwrap(), [
+            "j/clients",
+            "[[BATCH]]dispatch movetoworkspacesilent special:devchron,address:0xa;\
+             dispatch movetoworkspacesilent special:devchron,address:0xb;\
+             dispatch movetoworkspacesilent special:devchron,address:0xd",
+            "[[BATCH]]dispatch movetoworkspacesilent 2,address:0xa;\
+             dispatch movetoworkspacesilent name:chat,address:0xb;\
+             dispatch movetoworkspacesilent 4,address:0xd",
+        ]);
+    }
+
+    #[test]
+    fn quitting_mid_focus_restores_windows_and_border() {
+        let (client, requests) = fake_hyprland("quit");
+        let enforcer = FocusEnforcer::with_client(client, &settings(BlocklistAction::Dim, true), &Theme::nord());
+        enforcer.start_focus();
+        drop(enforcer);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 6);
+        assert_eq!(requests[0], "j/getoption general:col.active_border");
+        assert!(requests[1].starts_with("keyword general:col.active_border rgb("));
+        assert_eq!(requests[2], "j/clients");
+        assert_eq!(
+            requests[3],
+            "[[BATCH]]setprop address:0xa alpha 0.3;setprop address:0xb alpha 0.3;setprop address:0xd alpha 0.3"
+        );
+        assert_eq!(
+            requests[4],
+            "[[BATCH]]setprop address:0xa alpha 1.0;setprop address:0xb alpha 1.0;setprop address:0xd alpha 1.0"
+        );
+        assert_eq!(requests[5], "keyword general:col.active_border 0xee33ccff 0xee00ff99 45deg");
+    }
+
+    #[test]
+    fn unreachable_hyprland_does_not_block_shutdown() {
+        let client = HyprlandClient::with_socket_dir(PathBuf::from("/nonexistent/devchron-hypr"));
+        let enforcer = FocusEnforcer::with_client(client, &settings(BlocklistAction::Hide, true), &Theme::nord());
+        enforcer.start_focus();
+        enforcer.set_border(TimerPhase::ShortBreak);
+        drop(enforcer);
+    }
+}
@@ -1,76 +1,121 @@
-use std::fs;
-use std::path::PathBuf;
-use serde::{Deserialize, Serialize};
-use directories::ProjectDirs;
-use crate::timer::{PomodoroTimer, TimerPhase};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StatusUpdate {
-    pub phase: String,
-    pub time_remaining: String,
-    pub session: String,
-    pub is_running: bool,
-    pub percentage_complete: u16,
+use std::env;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+pub mod activity;
+pub mod focus;
+
+pub use activity::ActivityTracker;
+pub use focus::FocusEnforcer;
+
+/// Client for Hyprland's command socket (`.socket.sock`), the same one
+/// `hyprctl` talks to.
+#[derive(Debug, Clone)]
+pub struct HyprlandClient {
+    socket_dir: PathBuf,
 }
 
-pub struct StatusWriter {
-    enabled: bool,
-    cache_path: PathBuf,
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workspace {
+    pub id: i64,
+    pub name: String,
 }
 
-impl StatusWriter {
-    pub fn new(enabled: bool) -> Result<Self, std::io::Error> {
-        let cache_path = Self::get_cache_path()?;
-        Ok(Self { enabled, cache_path })
+impl Workspace {
+    /// How `dispatch workspace` and friends refer to this workspace.
+    pub fn selector(&self) -> String {
+        // Named workspaces need the `name:` prefix, special ones already
+        // carry theirs
+        if self.name == self.id.to_string() || self.name.starts_with("special") {
+            self.name.clone()
+        } else {
+            format!("name:{}", self.name)
+        }
     }
+}
 
-    fn get_cache_path() -> Result<PathBuf, std::io::Error> {
-        let proj_dirs = ProjectDirs::from("", "", "devchron")
-            .ok_or_else(|| {
-                std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Could not determine cache directory",
-                )
-            })?;
+/// A window, as listed by `j/clients`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Client {
+    pub address: String,
+    pub class: String,
+    pub workspace: Workspace,
+}
 
-        let cache_dir = proj_dirs.cache_dir();
-        fs::create_dir_all(cache_dir)?;
+impl HyprlandClient {
+    /// Locate the running Hyprland instance from
+    /// `$HYPRLAND_INSTANCE_SIGNATURE`. Returns `None` outside Hyprland.
+    pub fn from_env() -> Option<Self> {
+        let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
 
-        Ok(cache_dir.join("status.json"))
+        // Hyprland 0.40+ uses $XDG_RUNTIME_DIR/hypr, older releases /tmp/hypr
+        let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| PathBuf::from(dir).join("hypr").join(&signature))
+            .filter(|dir| dir.exists());
+        let socket_dir = runtime_dir.unwrap_or_else(|| Path::new("/tmp/hypr").join(&signature));
+
+        Some(Self::with_socket_dir(socket_dir))
     }
 
-    pub fn update(&self, timer: &PomodoroTimer) {
-        if !self.enabled {
-            return;
-        }
+    /// Talk to the sockets in `socket_dir` instead of the live instance.
+    pub fn with_socket_dir(socket_dir: PathBuf) -> Self {
+        Self { socket_dir }
+    }
 
-        let status = StatusUpdate {
-            phase: self.phase_to_string(timer.current_phase()),
-            time_remaining: timer.current_timer.format_time(),
-            session: timer.session_info(),
-            is_running: timer.is_running(),
-            percentage_complete: timer.current_timer.percentage_complete(),
-        };
-
-        if let Ok(json) = serde_json::to_string_pretty(&status) {
-            if let Err(e) = fs::write(&self.cache_path, json) {
-                eprintln!("Failed to write status file: {}", e);
-            }
-        }
+    pub fn event_socket_path(&self) -> PathBuf {
+        self.socket_dir.join(".socket2.sock")
     }
 
-    fn phase_to_string(&self, phase: TimerPhase) -> String {
-        match phase {
-            TimerPhase::Focus => "focus".to_string(),
-            TimerPhase::ShortBreak => "short_break".to_string(),
-            TimerPhase::LongBreak => "long_break".to_string(),
+    /// Send a raw request such as `dispatch workspace 3` and return the reply.
+    pub fn command(&self, request: &str) -> io::Result<String> {
+        let mut stream = UnixStream::connect(self.socket_dir.join(".socket.sock"))?;
+        stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+
+        stream.write_all(request.as_bytes())?;
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply)?;
+        Ok(reply)
+    }
+
+    /// Send several requests in one round trip.
+    pub fn batch(&self, requests: &[String]) -> io::Result<()> {
+        if requests.is_empty() {
+            return Ok(());
         }
+        let reply = self.command(&format!("[[BATCH]]{}", requests.join(";")))?;
+        check_reply(&reply)
+    }
+
+    pub fn dispatch(&self, args: &str) -> io::Result<()> {
+        check_reply(&self.command(&format!("dispatch {}", args))?)
+    }
+
+    /// Name of the focused workspace, suitable for `dispatch workspace`.
+    pub fn active_workspace(&self) -> io::Result<String> {
+        let workspace: Workspace = self.query("j/activeworkspace")?;
+        Ok(workspace.selector())
+    }
+
+    pub fn clients(&self) -> io::Result<Vec<Client>> {
+        self.query("j/clients")
+    }
+
+    /// Send a `j/` request and parse the JSON reply.
+    pub fn query<T: DeserializeOwned>(&self, request: &str) -> io::Result<T> {
+        let reply = self.command(request)?;
+        serde_json::from_str(&reply).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
 
-impl Drop for StatusWriter {
-    fn drop(&mut self) {
-        // Clean up status file on exit
-        let _ = fs::remove_file(&self.cache_path);
+fn check_reply(reply: &str) -> io::Result<()> {
+    // Successful requests reply `ok` (once per batched request)
+    match reply.split("\n\n").map(str::trim).find(|r| !r.is_empty() && *r != "ok") {
+        Some(error) => Err(io::Error::other(error.to_string())),
+        None => Ok(()),
     }
 }
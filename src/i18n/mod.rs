@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use directories::ProjectDirs;
+use crate::config::settings::UiSettings;
+use crate::error::{Error, Result};
+
+/// Catalogs compiled into the binary. Others are read from
+/// `~/.config/devchron/locales/<code>.toml`.
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("en.toml")),
+    ("ja", include_str!("ja.toml")),
+];
+
+type Catalog = HashMap<String, String>;
+
+/// UI and notification strings in the configured language, optionally
+/// paired with a second language for bilingual labels.
+#[derive(Debug, Clone)]
+pub struct Locale {
+    primary: Catalog,
+    secondary: Option<Catalog>,
+    fallback: Catalog,
+}
+
+impl Locale {
+    pub fn load(settings: &UiSettings) -> Result<Self> {
+        let primary = load_catalog(&settings.language)?;
+        let secondary = match settings.secondary_language.trim() {
+            "" => None,
+            code if code == settings.language => None,
+            code => Some(load_catalog(code)?),
+        };
+
+        Ok(Self {
+            primary,
+            secondary,
+            fallback: load_catalog("en")?,
+        })
+    }
+
+    /// The string for `key` in the primary language.
+    pub fn text(&self, key: &str) -> String {
+        self.lookup(&self.primary, key)
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// `text` with `{name}` placeholders replaced.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        fill(&self.text(key), args)
+    }
+
+    /// The secondary-language string, if bilingual labels are on, and the
+    /// primary one; rendered as `secondary [primary]`.
+    pub fn pair_with(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> (Option<String>, String) {
+        let secondary = self
+            .secondary
+            .as_ref()
+            .and_then(|catalog| catalog.get(key))
+            .map(|text| fill(text, args));
+        (secondary, self.format(key, args))
+    }
+
+    /// `pair_with` joined into one label.
+    pub fn label(&self, key: &str) -> String {
+        self.label_with(key, &[])
+    }
+
+    pub fn label_with(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        match self.pair_with(key, args) {
+            (Some(secondary), primary) => format!("{} [{}]", secondary, primary),
+            (None, primary) => primary,
+        }
+    }
+
+    fn lookup<'a>(&'a self, catalog: &'a Catalog, key: &str) -> Option<&'a str> {
+        catalog
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map(String::as_str)
+    }
+}
+
+fn fill(template: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+pub fn locales_dir() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("", "", "devchron")?;
+    Some(proj_dirs.config_dir().join("locales"))
+}
+
+/// A user catalog in the locales directory wins over a built-in one, so
+/// translations can be fixed without a rebuild.
+fn load_catalog(code: &str) -> Result<Catalog> {
+    // The code becomes a file name, so it must not reach outside the
+    // locales directory
+    if code.is_empty() || code.contains(['/', '\\']) || code.contains("..") {
+        return Err(Error::Config(format!(
+            "invalid language `{}`, codes can't contain path separators or `..`",
+            code
+        )));
+    }
+
+    let path = locales_dir()
+        .map(|dir| dir.join(format!("{}.toml", code)))
+        .filter(|path| path.exists());
+
+    let content = match (&path, BUILTIN.iter().find(|(name, _)| *name == code)) {
+        (Some(path), _) => fs::read_to_string(path)?,
+        (None, Some((_, content))) => content.to_string(),
+        (None, None) => {
+            return Err(Error::Config(format!(
+                "unknown language `{}`, expected one of: {} or a file in the locales directory",
+                code,
+                BUILTIN.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+            )))
+        }
+    };
+
+    let table: toml::Table = toml::from_str(&content).map_err(|e| match &path {
+        Some(path) => Error::Config(format!("{}: {}", path.display(), e)),
+        None => Error::Config(format!("built-in `{}` catalog: {}", code, e)),
+    })?;
+
+    let mut catalog = Catalog::new();
+    flatten("", &table, &mut catalog);
+    Ok(catalog)
+}
+
+/// `[stats] daily = "..."` becomes `stats.daily`.
+fn flatten(prefix: &str, table: &toml::Table, catalog: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::String(text) => {
+                catalog.insert(key, text.clone());
+            }
+            toml::Value::Table(table) => flatten(&key, table, catalog),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(code: &str) -> Catalog {
+        let (_, content) = BUILTIN.iter().find(|(name, _)| *name == code).unwrap();
+        let mut catalog = Catalog::new();
+        flatten("", &toml::from_str(content).unwrap(), &mut catalog);
+        catalog
+    }
+
+    #[test]
+    fn builtin_catalogs_cover_every_english_key() {
+        let english = builtin("en");
+        for (code, _) in BUILTIN {
+            let catalog = builtin(code);
+            let mut missing: Vec<_> = english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{}.toml is missing {:?}", code, missing);
+        }
+    }
+
+    #[test]
+    fn rejects_codes_that_leave_the_locales_directory() {
+        for code in ["", "../en", "ja/../../x", "..\\en"] {
+            match load_catalog(code) {
+                Err(Error::Config(message)) => assert!(message.contains("invalid language"), "{}", message),
+                other => panic!("{:?} was accepted: {:?}", code, other.map(|_| ())),
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use crate::config::settings::IdleSettings;
+use crate::error::{Error, Result};
+use crate::log;
+
+#[cfg(feature = "wayland-idle")]
+pub mod wayland;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleEvent {
+    /// The user went idle `already` ago, or as long ago as the threshold
+    /// when the source only knows it has passed
+    Idle { already: Option<Duration> },
+    Active,
+}
+
+/// A source of idle/active notifications.
+pub trait IdleBackend {
+    /// Begin delivering notifications. `threshold` is how long the user
+    /// must be inactive before devchron cares.
+    fn start(self: Box<Self>, threshold: Duration, events: UnboundedSender<IdleEvent>) -> Result<()>;
+}
+
+/// Relies on something outside devchron, such as hypridle's `on-timeout`
+/// and `on-resume`, running `devchron ctl idle` and `devchron ctl active`.
+pub struct ExternalBackend;
+
+impl IdleBackend for ExternalBackend {
+    fn start(self: Box<Self>, _threshold: Duration, _events: UnboundedSender<IdleEvent>) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub fn backend(name: &str) -> Result<Box<dyn IdleBackend>> {
+    match name.to_lowercase().as_str() {
+        "external" | "hypridle" => Ok(Box::new(ExternalBackend)),
+        #[cfg(feature = "wayland-idle")]
+        "wayland" => Ok(Box::new(wayland::WaylandBackend)),
+        #[cfg(not(feature = "wayland-idle"))]
+        "wayland" => Err(Error::Config(
+            "the wayland idle backend needs devchron built with --features wayland-idle".to_string(),
+        )),
+        other => Err(Error::Config(format!("unknown idle backend `{}`", other))),
+    }
+}
+
+/// Tracks how long the user has been idle, fed by an `IdleBackend` or by
+/// `handle` for notifications that arrive over the control socket.
+pub struct IdleMonitor {
+    threshold: Duration,
+    events: UnboundedReceiver<IdleEvent>,
+    idle_since: Option<Instant>,
+}
+
+impl IdleMonitor {
+    pub fn new(settings: &IdleSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+
+        let threshold = Duration::from_secs(settings.timeout_minutes * 60);
+        let backend = backend(&settings.backend).unwrap_or_else(|e| {
+            log::warning!("Idle detection falls back to `devchron ctl idle`: {}", e);
+            Box::new(ExternalBackend)
+        });
+        Some(Self::with_backend(threshold, backend))
+    }
+
+    /// A monitor fed by `backend`. If the backend can't start, only
+    /// events passed to `handle` are heard.
+    pub fn with_backend(threshold: Duration, backend: Box<dyn IdleBackend>) -> Self {
+        let (tx, events) = mpsc::unbounded_channel();
+        if let Err(e) = backend.start(threshold, tx) {
+            log::warning!("Idle detection falls back to `devchron ctl idle`: {}", e);
+        }
+
+        Self {
+            threshold,
+            events,
+            idle_since: None,
+        }
+    }
+
+    pub fn handle(&mut self, event: IdleEvent) {
+        match event {
+            IdleEvent::Idle { already } => {
+                // Keep the earliest start if we were already idle
+                if self.idle_since.is_none() {
+                    let already = already.unwrap_or(self.threshold);
+                    let now = Instant::now();
+                    self.idle_since = Some(now.checked_sub(already).unwrap_or(now));
+                }
+            }
+            IdleEvent::Active => self.idle_since = None,
+        }
+    }
+
+    /// How long the user has been idle, once that passes the threshold.
+    pub fn idle_for(&mut self) -> Option<Duration> {
+        while let Ok(event) = self.events.try_recv() {
+            self.handle(event);
+        }
+
+        let idle = self.idle_since?.elapsed();
+        (idle >= self.threshold).then_some(idle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Hands the event sender to the test, which plays the compositor.
+    struct FakeBackend {
+        sender: Arc<Mutex<Option<UnboundedSender<IdleEvent>>>>,
+    }
+
+    impl IdleBackend for FakeBackend {
+        fn start(self: Box<Self>, _threshold: Duration, events: UnboundedSender<IdleEvent>) -> Result<()> {
+            *self.sender.lock().unwrap() = Some(events);
+            Ok(())
+        }
+    }
+
+    struct BrokenBackend;
+
+    impl IdleBackend for BrokenBackend {
+        fn start(self: Box<Self>, _threshold: Duration, _events: UnboundedSender<IdleEvent>) -> Result<()> {
+            Err(Error::Config("compositor does not support ext-idle-notify-v1".to_string()))
+        }
+    }
+
+    const THRESHOLD: Duration = Duration::from_secs(300);
+
+    fn fake_monitor() -> (IdleMonitor, UnboundedSender<IdleEvent>) {
+        let sender = Arc::new(Mutex::new(None));
+        let monitor = IdleMonitor::with_backend(THRESHOLD, Box::new(FakeBackend { sender: sender.clone() }));
+        let events = sender.lock().unwrap().take().expect("backend was started");
+        (monitor, events)
+    }
+
+    #[test]
+    fn follows_idle_active_and_idle_again() {
+        let (mut monitor, events) = fake_monitor();
+        assert_eq!(monitor.idle_for(), None);
+
+        events.send(IdleEvent::Idle { already: Some(Duration::from_secs(600)) }).unwrap();
+        let idle = monitor.idle_for().expect("idle past the threshold");
+        assert!(idle >= Duration::from_secs(600) && idle < Duration::from_secs(610));
+
+        // A second report while still idle keeps the earlier start
+        events.send(IdleEvent::Idle { already: Some(Duration::ZERO) }).unwrap();
+        assert!(monitor.idle_for().unwrap() >= Duration::from_secs(600));
+
+        events.send(IdleEvent::Active).unwrap();
+        assert_eq!(monitor.idle_for(), None);
+
+        events.send(IdleEvent::Idle { already: None }).unwrap();
+        let idle = monitor.idle_for().expect("idle as long as the threshold");
+        assert!(idle >= THRESHOLD && idle < THRESHOLD + Duration::from_secs(10));
+    }
+
+    #[test]
+    fn short_idle_is_ignored() {
+        let (mut monitor, events) = fake_monitor();
+        events.send(IdleEvent::Idle { already: Some(Duration::from_secs(30)) }).unwrap();
+        assert_eq!(monitor.idle_for(), None);
+    }
+
+    #[test]
+    fn broken_backend_still_hears_control_commands() {
+        let mut monitor = IdleMonitor::with_backend(THRESHOLD, Box::new(BrokenBackend));
+        assert_eq!(monitor.idle_for(), None);
+
+        monitor.handle(IdleEvent::Idle { already: None });
+        assert!(monitor.idle_for().is_some());
+        monitor.handle(IdleEvent::Active);
+        assert_eq!(monitor.idle_for(), None);
+    }
+
+    #[test]
+    fn unknown_backend_falls_back_to_control_commands() {
+        let settings = IdleSettings {
+            enabled: true,
+            backend: "no-such-backend".to_string(),
+            ..IdleSettings::default()
+        };
+        assert!(IdleMonitor::new(&settings).is_some());
+    }
+}
@@ -0,0 +1,115 @@
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use wayland_client::protocol::{wl_registry, wl_seat};
+use wayland_client::{Connection, Dispatch, QueueHandle};
+use wayland_protocols::ext::idle_notify::v1::client::{
+    ext_idle_notification_v1, ext_idle_notifier_v1,
+};
+use crate::error::{Error, Result};
+use super::{IdleBackend, IdleEvent};
+
+/// Uses the compositor's `ext-idle-notify-v1` protocol directly.
+pub struct WaylandBackend;
+
+impl IdleBackend for WaylandBackend {
+    fn start(self: Box<Self>, threshold: Duration, events: UnboundedSender<IdleEvent>) -> Result<()> {
+        let conn = Connection::connect_to_env()
+            .map_err(|e| Error::Config(format!("could not connect to Wayland: {}", e)))?;
+
+        let mut queue = conn.new_event_queue();
+        let qh = queue.handle();
+        conn.display().get_registry(&qh, ());
+
+        let mut state = State {
+            seat: None,
+            notifier: None,
+            events,
+        };
+        queue
+            .roundtrip(&mut state)
+            .map_err(|e| Error::Config(format!("Wayland roundtrip failed: {}", e)))?;
+
+        let (Some(seat), Some(notifier)) = (&state.seat, &state.notifier) else {
+            return Err(Error::Config(
+                "compositor does not support ext-idle-notify-v1".to_string(),
+            ));
+        };
+        let timeout_ms = threshold.as_millis().min(u32::MAX as u128) as u32;
+        notifier.get_idle_notification(timeout_ms, seat, &qh, ());
+
+        thread::spawn(move || {
+            while queue.blocking_dispatch(&mut state).is_ok() {
+                if state.events.is_closed() {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+struct State {
+    seat: Option<wl_seat::WlSeat>,
+    notifier: Option<ext_idle_notifier_v1::ExtIdleNotifierV1>,
+    events: UnboundedSender<IdleEvent>,
+}
+
+impl Dispatch<wl_registry::WlRegistry, ()> for State {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _: &(),
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        if let wl_registry::Event::Global { name, interface, .. } = event {
+            match interface.as_str() {
+                "wl_seat" if state.seat.is_none() => {
+                    state.seat = Some(registry.bind(name, 1, qh, ()));
+                }
+                "ext_idle_notifier_v1" => {
+                    state.notifier = Some(registry.bind(name, 1, qh, ()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for State {
+    fn event(_: &mut Self, _: &wl_seat::WlSeat, _: wl_seat::Event, _: &(), _: &Connection, _: &QueueHandle<Self>) {}
+}
+
+impl Dispatch<ext_idle_notifier_v1::ExtIdleNotifierV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ext_idle_notifier_v1::ExtIdleNotifierV1,
+        _: ext_idle_notifier_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ext_idle_notification_v1::ExtIdleNotificationV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &ext_idle_notification_v1::ExtIdleNotificationV1,
+        event: ext_idle_notification_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let idle_event = match event {
+            // Fires once the user has been inactive for the full threshold
+            ext_idle_notification_v1::Event::Idled => IdleEvent::Idle { already: None },
+            ext_idle_notification_v1::Event::Resumed => IdleEvent::Active,
+            _ => return,
+        };
+        let _ = state.events.send(idle_event);
+    }
+}
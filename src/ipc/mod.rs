@@ -0,0 +1,97 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use directories::ProjectDirs;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc::UnboundedSender;
+use crate::error::{Error, Result};
+use crate::events::{parse_command, Action};
+use crate::log;
+
+/// Control socket that lets other processes drive the running timer with
+/// line-based text commands (`toggle`, `skip`, `extend 5`, ...).
+pub struct ControlServer {
+    socket_path: PathBuf,
+}
+
+impl ControlServer {
+    pub fn start(actions: UnboundedSender<Action>) -> Result<Self> {
+        let socket_path = socket_path()?;
+
+        if socket_path.exists() {
+            if std::os::unix::net::UnixStream::connect(&socket_path).is_ok() {
+                return Err(Error::Ipc(format!(
+                    "another instance is already listening on {}",
+                    socket_path.display()
+                )));
+            }
+            // Left behind by an instance that did not shut down cleanly
+            fs::remove_file(&socket_path)?;
+        }
+
+        let listener = UnixListener::bind(&socket_path)?;
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_client(stream, actions.clone()));
+            }
+        });
+
+        Ok(Self { socket_path })
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+async fn handle_client(stream: UnixStream, actions: UnboundedSender<Action>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        log::debug!("Control command: {}", line.trim());
+        let reply = match parse_command(&line) {
+            Some(action) if actions.send(action).is_ok() => "ok\n".to_string(),
+            Some(_) => "error: timer is shutting down\n".to_string(),
+            None => format!("error: unknown command `{}`\n", line.trim()),
+        };
+        if writer.write_all(reply.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Send one command to the running instance and return its reply.
+pub async fn send_command(command: &str) -> Result<String> {
+    let socket_path = socket_path()?;
+    let mut stream = UnixStream::connect(&socket_path).await.map_err(|e| {
+        Error::Ipc(format!("could not reach devchron at {}: {}", socket_path.display(), e))
+    })?;
+
+    stream.write_all(format!("{}\n", command).as_bytes()).await?;
+    stream.shutdown().await?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).await?;
+
+    let reply = reply.trim().to_string();
+    match reply.strip_prefix("error: ") {
+        Some(msg) => Err(Error::Ipc(msg.to_string())),
+        None => Ok(reply),
+    }
+}
+
+fn socket_path() -> Result<PathBuf> {
+    if let Some(runtime_dir) = env::var_os("XDG_RUNTIME_DIR") {
+        return Ok(PathBuf::from(runtime_dir).join("devchron.sock"));
+    }
+
+    let proj_dirs = ProjectDirs::from("", "", "devchron")
+        .ok_or_else(|| Error::Config("Could not determine cache directory".to_string()))?;
+    let cache_dir = proj_dirs.cache_dir();
+    fs::create_dir_all(cache_dir)?;
+    Ok(cache_dir.join("devchron.sock"))
+}
@@ -0,0 +1,117 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use directories::ProjectDirs;
+use super::Level;
+
+/// Size at which the log is rotated.
+const MAX_BYTES: u64 = 1024 * 1024;
+
+/// Rotated logs kept next to the current one, e.g. `devchron.log.1`
+/// (newest) up to `devchron.log.3`.
+const KEEP: usize = 3;
+
+/// A log such as `devchron.log` or `hooks.log`, appended to and rotated
+/// by size.
+pub struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl LogFile {
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    /// `$XDG_STATE_HOME/devchron/devchron.log`, or the data directory on
+    /// platforms without a state directory.
+    pub fn default_path() -> Option<PathBuf> {
+        let proj_dirs = ProjectDirs::from("", "", "devchron")?;
+        let dir = proj_dirs.state_dir().unwrap_or_else(|| proj_dirs.data_local_dir());
+        Some(dir.join("devchron.log"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// One line per message: time, level, the module it came from, text.
+    pub fn write(&mut self, level: Level, target: &str, text: &str) -> io::Result<()> {
+        let line = format!(
+            "{} {:<7} {}: {}\n",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            level.as_str().to_uppercase(),
+            target,
+            text.replace('\n', "\n    ")
+        );
+        self.append(&line)
+    }
+
+    /// Add `text` as is, rotating first if the log is full.
+    pub fn append(&mut self, text: &str) -> io::Result<()> {
+        if self.size >= MAX_BYTES {
+            self.rotate()?;
+        }
+        self.file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let rotated = |n: usize| PathBuf::from(format!("{}.{}", self.path.display(), n));
+        for n in (1..KEEP).rev() {
+            if rotated(n).exists() {
+                fs::rename(rotated(n), rotated(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_and_keeps_three_old_logs() {
+        let dir = std::env::temp_dir().join(format!("devchron-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("hooks.log");
+        let rotated = |n: usize| dir.join(format!("hooks.log.{}", n));
+
+        let chunk = "x".repeat(64 * 1024);
+        let mut log = LogFile::open(path.clone()).unwrap();
+        for round in 0..5 {
+            // Fill the log to the cap, then mark the next one
+            for _ in 0..MAX_BYTES / chunk.len() as u64 {
+                log.append(&chunk).unwrap();
+            }
+            log.append(&format!("round {}\n", round)).unwrap();
+        }
+
+        let size = |path: &Path| fs::metadata(path).unwrap().len();
+        assert!(size(&path) < MAX_BYTES);
+        assert!(fs::read_to_string(&path).unwrap().ends_with("round 4\n"));
+        for n in 1..=KEEP {
+            assert!(size(&rotated(n)) <= MAX_BYTES + 16);
+        }
+        assert!(fs::read_to_string(rotated(1)).unwrap().starts_with("round 3\n"));
+        assert!(!rotated(KEEP + 1).exists());
+
+        // Reopening picks up the current size rather than starting over
+        let log = LogFile::open(path.clone()).unwrap();
+        assert_eq!(log.size, size(&path));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
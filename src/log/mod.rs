@@ -0,0 +1,138 @@
+mod file;
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+pub use file::LogFile;
+
+/// How serious a message is. Info and up are shown to the user, as toasts
+/// or on stderr; the log file takes whatever `--log-level` lets through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" | "trace" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "warn" | "warning" => Ok(Level::Warning),
+            "error" => Ok(Level::Error),
+            _ => Err(format!("unknown log level `{}`, expected debug, info, warning or error", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub level: Level,
+    pub text: String,
+}
+
+/// Where messages go while the TUI owns the terminal. Without it they are
+/// printed to stderr.
+static CHANNEL: Mutex<Option<UnboundedSender<Message>>> = Mutex::new(None);
+
+/// The log file and the lowest level written to it.
+static FILE: Mutex<Option<(LogFile, Level)>> = Mutex::new(None);
+
+/// Starts writing messages at `threshold` and above to the log file.
+pub fn init(threshold: Level) -> io::Result<PathBuf> {
+    let path = LogFile::default_path().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "Could not determine state directory")
+    })?;
+    let file = LogFile::open(path)?;
+    let path = file.path().to_path_buf();
+    *FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some((file, threshold));
+    Ok(path)
+}
+
+/// Records a message from the module `target`. Use the macros, which fill
+/// in the module.
+pub fn emit(level: Level, target: &str, text: String) {
+    {
+        let mut file = FILE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((log_file, threshold)) = file.as_mut() {
+            // A log that can't be written is dropped rather than reported,
+            // reporting it would only try to write to it again
+            if level >= *threshold && log_file.write(level, target, &text).is_err() {
+                *file = None;
+            }
+        }
+    }
+
+    if level < Level::Info {
+        return;
+    }
+
+    let message = Message { level, text };
+    let channel = CHANNEL.lock().unwrap_or_else(|e| e.into_inner());
+    let unsent = match channel.as_ref() {
+        Some(sender) => sender.send(message).err().map(|e| e.0),
+        None => Some(message),
+    };
+    if let Some(message) = unsent {
+        print(&message);
+    }
+}
+
+pub fn print(message: &Message) {
+    eprintln!("{}: {}", message.level, message.text);
+}
+
+/// Routes messages to the returned receiver until `release`, so they can
+/// be shown inside the TUI instead of corrupting it.
+pub fn capture() -> UnboundedReceiver<Message> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    *CHANNEL.lock().unwrap_or_else(|e| e.into_inner()) = Some(sender);
+    receiver
+}
+
+pub fn release() {
+    *CHANNEL.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log::emit($crate::log::Level::Debug, module_path!(), format!($($arg)*)) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log::emit($crate::log::Level::Info, module_path!(), format!($($arg)*)) };
+}
+
+macro_rules! warning {
+    ($($arg:tt)*) => { $crate::log::emit($crate::log::Level::Warning, module_path!(), format!($($arg)*)) };
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::log::emit($crate::log::Level::Error, module_path!(), format!($($arg)*)) };
+}
+
+pub(crate) use {debug, error, info, warning};
@@ -4,13 +4,26 @@ mod timer;
 mod ui;
 mod events;
 mod notification;
+mod status;
 mod hyprland;
+mod hooks;
+mod history;
+mod i18n;
+mod idle;
+mod breaks;
+#[cfg(feature = "dbus")]
+mod dbus;
+mod mpris;
+mod ipc;
+mod cli;
 mod app;
+mod log;
 
 use std::io;
 use std::time::Duration;
+use clap::Parser;
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -18,49 +31,150 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
+use tokio::sync::mpsc::{self, UnboundedReceiver};
 use tokio::time::interval;
 
 use app::App;
+use cli::{Cli, Command, ThemesCommand};
 use config::Config;
-use events::{handle_key, Action};
-use error::Result;
+use events::{handle_mouse, Action};
+use error::{Error, Result};
+use ipc::ControlServer;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    if let Err(e) = log::init(cli.log_level.unwrap_or_default()) {
+        log::warning!("Logging to file disabled: {}", e);
+    }
+
+    if let Some(command) = cli.command {
+        return run_command(command).await;
+    }
+
     // Load configuration
     let config = Config::load()?;
+    log::debug!("Starting devchron {}", env!("CARGO_PKG_VERSION"));
+
+    // Diagnostics become toasts while the TUI runs; whatever is left when
+    // it exits goes to stderr
+    let mut messages = log::capture();
+    let result = run_tui(config, &mut messages).await;
+    log::release();
+    while let Ok(message) = messages.try_recv() {
+        log::print(&message);
+    }
+    result
+}
+
+async fn run_tui(config: Config, messages: &mut UnboundedReceiver<log::Message>) -> Result<()> {
+    // Listen for commands from other processes
+    let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+    #[cfg(feature = "dbus")]
+    let dbus_actions = action_tx.clone();
+    let _control_server = match ControlServer::start(action_tx) {
+        Ok(server) => Some(server),
+        Err(e) => {
+            log::warning!("Control socket disabled: {}", e);
+            None
+        }
+    };
+
+    // Create app before touching the terminal so config errors print cleanly
+    let mouse = config.settings.ui.mouse;
+    let mut app = App::new(config)?;
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app
-    let mut app = App::new(config)?;
+    #[cfg(feature = "dbus")]
+    match dbus::DbusSink::start(dbus_actions).await {
+        Ok(sink) => app.add_status_sink(Box::new(sink)),
+        Err(e) => log::warning!("D-Bus service disabled: {}", e),
+    }
 
     // Run app
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = run_app(&mut terminal, &mut app, &mut action_rx, messages).await;
+    app.shutdown().await;
 
     // Restore terminal
     disable_raw_mode()?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     result
 }
 
+async fn run_command(command: Command) -> Result<()> {
+    match command {
+        Command::Ctl { command } => {
+            let reply = ipc::send_command(&command.join(" ")).await?;
+            println!("{}", reply);
+        }
+        Command::Waybar => {
+            let config = Config::load()?;
+            let status_path = status::StatusWriter::status_path(&config.settings.integrations)?;
+            status::waybar::run(&config.settings.waybar, &status_path).await?;
+        }
+        Command::Status { format } => {
+            let config = Config::load()?;
+            let theme = ui::Theme::load(&config.settings.ui)?;
+            let formatter = status::format::formatter(&format, &theme, &config.settings.waybar)
+                .ok_or_else(|| Error::Config(format!(
+                    "unknown format `{}`, expected one of: {}",
+                    format,
+                    status::format::FORMAT_NAMES.join(", ")
+                )))?;
+
+            let status_path = status::StatusWriter::status_path(&config.settings.integrations)?;
+            match status::StatusUpdate::read(&status_path) {
+                Some(status) => println!("{}", formatter.format(&status)),
+                None => println!("{}", formatter.inactive()),
+            }
+        }
+        Command::Themes { command } => {
+            let config = Config::load()?;
+            match command {
+                ThemesCommand::List => ui::preview::print_list(&config.settings.ui.theme),
+                ThemesCommand::Preview { name } => {
+                    // Without a name, preview the configured theme with its overrides
+                    let theme = match &name {
+                        Some(name) => ui::Theme::from_name(name)?,
+                        None => ui::Theme::load(&config.settings.ui)?,
+                    };
+                    let locale = i18n::Locale::load(&config.settings.ui)?;
+                    ui::preview::print_preview(name.as_deref().unwrap_or(&config.settings.ui.theme), &theme, &locale);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    actions: &mut UnboundedReceiver<Action>,
+    messages: &mut UnboundedReceiver<log::Message>,
 ) -> Result<()> {
     let mut tick_interval = interval(Duration::from_secs(1));
 
     loop {
-        // Render UI
+        // Render UI, keeping what's clickable for mouse events
+        let mut hitboxes = ui::hitbox::Hitboxes::default();
         terminal.draw(|f| {
-            ui::render(f, &app.timer, &app.theme, app.show_help);
+            hitboxes = ui::render(f, app);
         })?;
 
         // Handle events
@@ -68,18 +182,19 @@ async fn run_app<B: ratatui::backend::Backend>(
             _ = tick_interval.tick() => {
                 app.tick();
             }
+            Some(action) = actions.recv() => {
+                app.handle_action(action);
+            }
+            Some(message) = messages.recv() => {
+                app.toasts.push(message);
+            }
             _ = tokio::time::sleep(Duration::from_millis(100)) => {
-                if event::poll(Duration::from_millis(0))? {
-                    if let Event::Key(key) = event::read()? {
-                        let action = handle_key(key);
-                        match action {
-                            Action::Quit => app.quit(),
-                            Action::TogglePause => app.toggle_pause(),
-                            Action::Reset => app.reset(),
-                            Action::Skip => app.skip(),
-                            Action::ToggleHelp => app.toggle_help(),
-                            Action::None => {}
-                        }
+                // Drain everything queued, mouse motion can arrive in bursts
+                while event::poll(Duration::from_millis(0))? {
+                    match event::read()? {
+                        Event::Key(key) => app.handle_key(key),
+                        Event::Mouse(mouse) => app.handle_action(handle_mouse(mouse, &hitboxes)),
+                        _ => {}
                     }
                 }
             }
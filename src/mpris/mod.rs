@@ -0,0 +1,86 @@
+use crate::config::settings::{MprisMode, MprisSettings};
+
+#[cfg(feature = "mpris")]
+mod player;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaCommand {
+    /// Pause playing players and remember them
+    PausePlaying,
+    /// Resume the players paused by `PausePlaying`
+    ResumePaused,
+    /// Start every allowed player
+    PlayAll,
+    /// Pause every allowed player
+    PauseAll,
+}
+
+/// Pauses or plays MPRIS media players around focus phases.
+///
+/// Commands are handed to a background task so a slow player never
+/// blocks the UI.
+pub struct MprisController {
+    mode: MprisMode,
+    #[cfg(feature = "mpris")]
+    commands: tokio::sync::mpsc::UnboundedSender<MediaCommand>,
+    #[cfg(feature = "mpris")]
+    worker: tokio::task::JoinHandle<()>,
+}
+
+impl MprisController {
+    /// Returns `None` when disabled or built without the `mpris` feature.
+    pub fn new(settings: &MprisSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+
+        #[cfg(feature = "mpris")]
+        {
+            let (commands, worker) = player::spawn(settings.players.clone());
+            Some(Self {
+                mode: settings.mode,
+                commands,
+                worker,
+            })
+        }
+
+        #[cfg(not(feature = "mpris"))]
+        {
+            crate::log::warning!("MPRIS control needs devchron built with --features mpris");
+            None
+        }
+    }
+
+    pub fn focus_started(&self) {
+        match self.mode {
+            MprisMode::PauseDuringFocus => self.send(MediaCommand::PausePlaying),
+            MprisMode::PauseOnBreak => self.send(MediaCommand::ResumePaused),
+            MprisMode::PlayDuringFocus => self.send(MediaCommand::PlayAll),
+        }
+    }
+
+    pub fn focus_ended(&self) {
+        match self.mode {
+            MprisMode::PauseDuringFocus => self.send(MediaCommand::ResumePaused),
+            MprisMode::PauseOnBreak => self.send(MediaCommand::PausePlaying),
+            MprisMode::PlayDuringFocus => self.send(MediaCommand::PauseAll),
+        }
+    }
+
+    /// Resume the players we paused and wait, briefly, for that to happen.
+    pub async fn shutdown(self) {
+        #[cfg(feature = "mpris")]
+        {
+            drop(self.commands);
+            let _ = tokio::time::timeout(std::time::Duration::from_secs(2), self.worker).await;
+        }
+    }
+
+    #[cfg(feature = "mpris")]
+    fn send(&self, command: MediaCommand) {
+        let _ = self.commands.send(command);
+    }
+
+    #[cfg(not(feature = "mpris"))]
+    fn send(&self, _command: MediaCommand) {}
+}
@@ -0,0 +1,340 @@
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use zbus::fdo::DBusProxy;
+use zbus::{Connection, Proxy};
+use super::MediaCommand;
+use crate::log;
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+/// Start the worker that talks to players on the session bus. It stops,
+/// resuming whatever it paused, once the sender is dropped.
+pub fn spawn(allowlist: Vec<String>) -> (UnboundedSender<MediaCommand>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let worker = tokio::spawn(async move {
+        match Connection::session().await {
+            Ok(connection) => run(&SessionBus { connection }, &allowlist, rx).await,
+            Err(e) => log::warning!("MPRIS: could not connect to the session bus: {}", e),
+        }
+    });
+    (tx, worker)
+}
+
+/// What the worker needs from the bus, so tests can stand in for players.
+trait Players {
+    /// Bus names of every running MPRIS player.
+    async fn list(&self) -> zbus::Result<Vec<String>>;
+    async fn playback_status(&self, player: &str) -> zbus::Result<String>;
+    async fn call(&self, player: &str, method: &str) -> zbus::Result<()>;
+}
+
+struct SessionBus {
+    connection: Connection,
+}
+
+impl SessionBus {
+    async fn player_proxy<'a>(&self, player: &'a str) -> zbus::Result<Proxy<'a>> {
+        Proxy::new(&self.connection, player, MPRIS_PATH, PLAYER_INTERFACE).await
+    }
+}
+
+impl Players for SessionBus {
+    async fn list(&self) -> zbus::Result<Vec<String>> {
+        let names = DBusProxy::new(&self.connection).await?.list_names().await?;
+        Ok(names
+            .into_iter()
+            .map(|name| name.to_string())
+            .filter(|name| name.starts_with(MPRIS_PREFIX))
+            .collect())
+    }
+
+    async fn playback_status(&self, player: &str) -> zbus::Result<String> {
+        self.player_proxy(player).await?.get_property("PlaybackStatus").await
+    }
+
+    async fn call(&self, player: &str, method: &str) -> zbus::Result<()> {
+        self.player_proxy(player).await?.call_method(method, &()).await?;
+        Ok(())
+    }
+}
+
+async fn run(bus: &impl Players, allowlist: &[String], mut commands: UnboundedReceiver<MediaCommand>) {
+    // Players we paused, so we only resume what we stopped
+    let mut paused_by_us: Vec<String> = Vec::new();
+
+    while let Some(command) = commands.recv().await {
+        let players = match bus.list().await {
+            Ok(names) => allowed(names, allowlist),
+            Err(e) => {
+                log::warning!("MPRIS: could not list players: {}", e);
+                continue;
+            }
+        };
+
+        let result = match command {
+            MediaCommand::PausePlaying => {
+                let mut result = Ok(());
+                for player in players {
+                    if bus.playback_status(&player).await.as_deref() == Ok("Playing") {
+                        // A player that ignored us is still the user's to resume
+                        match bus.call(&player, "Pause").await {
+                            Ok(()) => paused_by_us.push(player),
+                            Err(e) => result = Err(e),
+                        }
+                    }
+                }
+                result
+            }
+            MediaCommand::ResumePaused => resume(bus, &mut paused_by_us, &players).await,
+            MediaCommand::PlayAll => call_all(bus, &players, "Play").await,
+            MediaCommand::PauseAll => call_all(bus, &players, "Pause").await,
+        };
+
+        match result {
+            Ok(()) => log::debug!("MPRIS: {:?} done", command),
+            Err(e) => log::warning!("MPRIS: {}", e),
+        }
+    }
+
+    // Shutting down: don't leave anything paused that we paused
+    if paused_by_us.is_empty() {
+        return;
+    }
+    let result = match bus.list().await {
+        Ok(names) => resume(bus, &mut paused_by_us, &names).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warning!("MPRIS: could not resume players on exit: {}", e);
+    }
+}
+
+/// Play the players in `paused_by_us` that are still running.
+async fn resume(bus: &impl Players, paused_by_us: &mut Vec<String>, running: &[String]) -> zbus::Result<()> {
+    let mut result = Ok(());
+    for player in paused_by_us.drain(..).filter(|p| running.contains(p)) {
+        if let Err(e) = bus.call(&player, "Play").await {
+            result = Err(e);
+        }
+    }
+    result
+}
+
+/// Players limited to the allowlist when one is set. Entries match the
+/// name after `org.mpris.MediaPlayer2.`, so `firefox` also covers
+/// `firefox.instance_1_23`.
+fn allowed(names: Vec<String>, allowlist: &[String]) -> Vec<String> {
+    names
+        .into_iter()
+        .filter(|name| {
+            let Some(player) = name.strip_prefix(MPRIS_PREFIX) else {
+                return false;
+            };
+            allowlist.is_empty()
+                || allowlist.iter().any(|allowed| {
+                    player == allowed || player.starts_with(&format!("{}.", allowed))
+                })
+        })
+        .collect()
+}
+
+async fn call_all(bus: &impl Players, players: &[String], method: &str) -> zbus::Result<()> {
+    let mut result = Ok(());
+    for player in players {
+        if let Err(e) = bus.call(player, method).await {
+            result = Err(e);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::os::unix::net::UnixStream;
+    use std::sync::{Arc, Mutex};
+    use zbus::{connection, Guid};
+
+    /// Players as name and playback status. Calls are recorded, and the
+    /// ones listed in `refusing` fail.
+    struct FakePlayers {
+        players: Mutex<BTreeMap<String, String>>,
+        refusing: Vec<String>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FakePlayers {
+        fn new(players: &[(&str, &str)], refusing: &[&str]) -> Self {
+            Self {
+                players: Mutex::new(
+                    players
+                        .iter()
+                        .map(|(name, status)| (format!("{}{}", MPRIS_PREFIX, name), status.to_string()))
+                        .collect(),
+                ),
+                refusing: refusing.iter().map(|name| format!("{}{}", MPRIS_PREFIX, name)).collect(),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl Players for FakePlayers {
+        async fn list(&self) -> zbus::Result<Vec<String>> {
+            Ok(self.players.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn playback_status(&self, player: &str) -> zbus::Result<String> {
+            Ok(self.players.lock().unwrap()[player].clone())
+        }
+
+        async fn call(&self, player: &str, method: &str) -> zbus::Result<()> {
+            let name = player.strip_prefix(MPRIS_PREFIX).unwrap();
+            self.calls.lock().unwrap().push(format!("{} {}", name, method));
+            if self.refusing.iter().any(|p| p == player) {
+                return Err(zbus::Error::Failure("not now".to_string()));
+            }
+            let status = if method == "Play" { "Playing" } else { "Paused" };
+            self.players.lock().unwrap().insert(player.to_string(), status.to_string());
+            Ok(())
+        }
+    }
+
+    async fn run_commands(bus: &impl Players, allowlist: &[&str], commands: &[MediaCommand]) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        for command in commands {
+            tx.send(*command).unwrap();
+        }
+        drop(tx);
+        let allowlist: Vec<String> = allowlist.iter().map(|s| s.to_string()).collect();
+        run(bus, &allowlist, rx).await;
+    }
+
+    #[tokio::test]
+    async fn resumes_only_what_it_paused() {
+        let bus = FakePlayers::new(&[("mpv", "Playing"), ("spotify", "Paused")], &[]);
+        run_commands(&bus, &[], &[MediaCommand::PausePlaying, MediaCommand::ResumePaused]).await;
+        assert_eq!(bus.calls(), ["mpv Pause", "mpv Play"]);
+    }
+
+    #[tokio::test]
+    async fn failed_pause_is_not_resumed_later() {
+        let bus = FakePlayers::new(&[("mpv", "Playing"), ("spotify", "Playing")], &["spotify"]);
+        run_commands(&bus, &[], &[MediaCommand::PausePlaying, MediaCommand::ResumePaused]).await;
+        assert_eq!(bus.calls(), ["mpv Pause", "spotify Pause", "mpv Play"]);
+    }
+
+    #[tokio::test]
+    async fn resumes_paused_players_on_shutdown() {
+        let bus = FakePlayers::new(&[("mpv", "Playing"), ("spotify", "Playing")], &[]);
+        run_commands(&bus, &["mpv"], &[MediaCommand::PausePlaying]).await;
+        assert_eq!(bus.calls(), ["mpv Pause", "mpv Play"]);
+    }
+
+    #[tokio::test]
+    async fn nothing_to_resume_on_shutdown() {
+        let bus = FakePlayers::new(&[("mpv", "Playing")], &[]);
+        run_commands(&bus, &[], &[MediaCommand::PausePlaying, MediaCommand::ResumePaused]).await;
+        run_commands(&bus, &[], &[]).await;
+        assert_eq!(bus.calls(), ["mpv Pause", "mpv Play"]);
+    }
+
+    /// Stands in for the bus daemon on a peer-to-peer connection, which
+    /// has none to answer `ListNames`.
+    struct MockBus {
+        names: Vec<String>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.DBus")]
+    impl MockBus {
+        fn list_names(&self) -> Vec<String> {
+            self.names.clone()
+        }
+    }
+
+    /// A player answering whatever bus name it's called by, so one
+    /// connection can only reach one of them.
+    struct MockPlayer {
+        status: String,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl MockPlayer {
+        fn play(&mut self) {
+            self.calls.lock().unwrap().push("Play".to_string());
+            self.status = "Playing".to_string();
+        }
+
+        fn pause(&mut self) {
+            self.calls.lock().unwrap().push("Pause".to_string());
+            self.status = "Paused".to_string();
+        }
+
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status.clone()
+        }
+    }
+
+    /// A `SessionBus` talking to a mock player over a socket pair.
+    async fn mock_player(status: &str) -> (SessionBus, Arc<Mutex<Vec<String>>>, Connection) {
+        let (server, client) = UnixStream::pair().unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let bus = MockBus {
+            names: vec![format!("{}mpv", MPRIS_PREFIX), "org.freedesktop.Notifications".to_string()],
+        };
+        let player = MockPlayer {
+            status: status.to_string(),
+            calls: calls.clone(),
+        };
+        let server = connection::Builder::unix_stream(server)
+            .server(Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at("/org/freedesktop/DBus", bus)
+            .unwrap()
+            .serve_at(MPRIS_PATH, player)
+            .unwrap()
+            .build();
+        let client = connection::Builder::unix_stream(client).p2p().build();
+        let (server, client) = tokio::join!(server, client);
+        (SessionBus { connection: client.unwrap() }, calls, server.unwrap())
+    }
+
+    #[tokio::test]
+    async fn pauses_and_resumes_a_player_over_dbus() {
+        let (bus, calls, _server) = mock_player("Playing").await;
+        assert_eq!(bus.list().await.unwrap(), [format!("{}mpv", MPRIS_PREFIX)]);
+        run_commands(&bus, &[], &[MediaCommand::PausePlaying, MediaCommand::ResumePaused]).await;
+        assert_eq!(*calls.lock().unwrap(), ["Pause", "Play"]);
+    }
+
+    #[tokio::test]
+    async fn leaves_a_stopped_player_alone_over_dbus() {
+        let (bus, calls, _server) = mock_player("Paused").await;
+        run_commands(&bus, &[], &[MediaCommand::PausePlaying, MediaCommand::ResumePaused]).await;
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn allowlist_matches_player_and_instances() {
+        let names = ["firefox.instance_1_23", "firefoxdev", "spotify"]
+            .iter()
+            .map(|name| format!("{}{}", MPRIS_PREFIX, name))
+            .chain(["org.freedesktop.Notifications".to_string()])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            allowed(names.clone(), &["firefox".to_string()]),
+            [format!("{}firefox.instance_1_23", MPRIS_PREFIX)]
+        );
+        assert_eq!(allowed(names, &[]).len(), 3);
+    }
+}
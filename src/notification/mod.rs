@@ -1,47 +1,61 @@
 use notify_rust::{Notification, Timeout, Urgency};
+use crate::i18n::Locale;
 use crate::timer::TimerPhase;
+use crate::log;
 
 pub struct NotificationManager {
     enabled: bool,
+    locale: Locale,
 }
 
 impl NotificationManager {
-    pub fn new(enabled: bool) -> Self {
-        Self { enabled }
+    pub fn new(enabled: bool, locale: Locale) -> Self {
+        Self { enabled, locale }
     }
 
-    pub fn send_phase_complete(&self, phase: TimerPhase) {
+    pub fn send_phase_complete(&self, phase: TimerPhase, focus_score: Option<u8>) {
         if !self.enabled {
             return;
         }
 
-        let (summary, body, urgency) = match phase {
-            TimerPhase::Focus => (
-                "🍅 Focus Time Complete!",
-                "Great work! Time for a break.",
-                Urgency::Normal,
-            ),
-            TimerPhase::ShortBreak => (
-                "☕ Break Over",
-                "Ready to focus again?",
-                Urgency::Normal,
-            ),
-            TimerPhase::LongBreak => (
-                "🌴 Long Break Complete",
-                "Feeling refreshed? Let's get back to work!",
-                Urgency::Low,
-            ),
+        let (key, urgency) = match phase {
+            TimerPhase::Focus => ("notify.focus_complete", Urgency::Normal),
+            TimerPhase::ShortBreak => ("notify.short_break_complete", Urgency::Normal),
+            TimerPhase::LongBreak => ("notify.long_break_complete", Urgency::Low),
         };
 
-        if let Err(e) = Notification::new()
+        let mut body = self.locale.text(&format!("{}_body", key));
+        if let Some(score) = focus_score {
+            body.push('\n');
+            body.push_str(&self.locale.format("notify.focus_score", &[("score", &score)]));
+        }
+
+        self.show(&self.locale.text(key), &body, urgency);
+    }
+
+    pub fn send_idle_paused(&self, idle_minutes: u64) {
+        if !self.enabled {
+            return;
+        }
+
+        self.show(
+            &self.locale.text("notify.idle_paused"),
+            &self.locale.format("notify.idle_paused_body", &[("minutes", &idle_minutes)]),
+            Urgency::Normal,
+        );
+    }
+
+    fn show(&self, summary: &str, body: &str, urgency: Urgency) {
+        let result = Notification::new()
             .summary(summary)
             .body(body)
             .icon("clock")
             .urgency(urgency)
             .timeout(Timeout::Milliseconds(5000))
-            .show()
-        {
-            eprintln!("Failed to send notification: {}", e);
+            .show();
+        match result {
+            Ok(_) => log::debug!("Sent notification: {}", summary),
+            Err(e) => log::warning!("Failed to send notification: {}", e),
         }
     }
 }
@@ -0,0 +1,209 @@
+use ratatui::style::Color;
+use crate::config::settings::WaybarSettings;
+use crate::ui::theme::{to_hex, Theme};
+use super::StatusUpdate;
+use super::waybar::WaybarOutput;
+
+pub const FORMAT_NAMES: &[&str] = &["json", "waybar", "polybar", "i3blocks", "eww", "tmux"];
+
+/// Renders a `StatusUpdate` into the text a particular status bar expects.
+pub trait StatusFormatter {
+    fn format(&self, status: &StatusUpdate) -> String;
+
+    /// Output used when no timer is running.
+    fn inactive(&self) -> String {
+        String::new()
+    }
+}
+
+/// Look up a built-in formatter by name.
+pub fn formatter(
+    name: &str,
+    theme: &Theme,
+    waybar: &WaybarSettings,
+) -> Option<Box<dyn StatusFormatter>> {
+    let formatter: Box<dyn StatusFormatter> = match name.to_lowercase().as_str() {
+        "json" => Box::new(JsonFormatter),
+        "waybar" => Box::new(WaybarFormatter { settings: waybar.clone() }),
+        "polybar" => Box::new(PolybarFormatter { theme: *theme }),
+        "i3blocks" => Box::new(I3blocksFormatter { theme: *theme }),
+        "eww" => Box::new(EwwFormatter),
+        "tmux" => Box::new(TmuxFormatter { theme: *theme }),
+        _ => return None,
+    };
+    Some(formatter)
+}
+
+pub struct JsonFormatter;
+
+impl StatusFormatter for JsonFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        serde_json::to_string_pretty(status).unwrap_or_default()
+    }
+}
+
+pub struct WaybarFormatter {
+    settings: WaybarSettings,
+}
+
+impl StatusFormatter for WaybarFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        serde_json::to_string(&WaybarOutput::from_status(status, &self.settings)).unwrap_or_default()
+    }
+
+    fn inactive(&self) -> String {
+        serde_json::to_string(&WaybarOutput::inactive(&self.settings)).unwrap_or_default()
+    }
+}
+
+/// `%{F#rrggbb}🍅 23:45%{F-}` for a Polybar `custom/script` module.
+pub struct PolybarFormatter {
+    theme: Theme,
+}
+
+impl StatusFormatter for PolybarFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        format!(
+            "%{{F{}}}{} {}%{{F-}}",
+            to_hex(status_color(status, &self.theme)),
+            phase_icon(status),
+            status.time_remaining
+        )
+    }
+}
+
+/// i3blocks' three-line protocol: full text, short text, color.
+pub struct I3blocksFormatter {
+    theme: Theme,
+}
+
+impl StatusFormatter for I3blocksFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        format!(
+            "{} {} ({})\n{}\n{}",
+            phase_icon(status),
+            status.time_remaining,
+            status.session,
+            status.time_remaining,
+            to_hex(status_color(status, &self.theme))
+        )
+    }
+}
+
+/// One `name=value` per line, e.g. for `xargs -d '\n' eww update`.
+pub struct EwwFormatter;
+
+impl StatusFormatter for EwwFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        [
+            format!("devchron_phase={}", status.phase),
+            format!("devchron_state={}", status.state),
+            format!("devchron_time={}", status.time_remaining),
+            format!("devchron_remaining_seconds={}", status.remaining_seconds),
+            format!("devchron_ends_at={}", status.phase_ends_at.as_deref().unwrap_or("")),
+            format!("devchron_session={}", status.session),
+            format!("devchron_running={}", status.is_running),
+            format!("devchron_overtime={}", status.is_overtime),
+            format!("devchron_percentage={}", status.percentage_complete),
+            format!("devchron_today={}", status.today_focus),
+            format!("devchron_today_sessions={}", status.today_sessions),
+        ]
+        .join("\n")
+    }
+
+    fn inactive(&self) -> String {
+        "devchron_phase=inactive".to_string()
+    }
+}
+
+/// `#[fg=#rrggbb]🍅 23:45#[default]` for tmux's `status-right`.
+pub struct TmuxFormatter {
+    theme: Theme,
+}
+
+impl StatusFormatter for TmuxFormatter {
+    fn format(&self, status: &StatusUpdate) -> String {
+        format!(
+            "#[fg={}]{} {}#[default]",
+            to_hex(status_color(status, &self.theme)),
+            phase_icon(status),
+            status.time_remaining
+        )
+    }
+}
+
+/// Shown for a paused timer, and for phases we don't know.
+pub const PAUSED_ICON: &str = "⏸";
+
+/// How a phase appears in status bars: icon, label and Waybar CSS class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseDisplay {
+    pub icon: &'static str,
+    pub label: &'static str,
+    pub class: &'static str,
+}
+
+/// The display for a `StatusUpdate::phase`. Every formatter goes through
+/// this, so bars never disagree about a phase.
+pub fn phase_display(phase: &str) -> PhaseDisplay {
+    let (icon, label, class) = match phase {
+        "focus" => ("🍅", "Focus", "focus"),
+        "short_break" => ("☕", "Short break", "short-break"),
+        "long_break" => ("🌴", "Long break", "long-break"),
+        _ => (PAUSED_ICON, "Inactive", "inactive"),
+    };
+    PhaseDisplay { icon, label, class }
+}
+
+fn phase_icon(status: &StatusUpdate) -> &'static str {
+    if !status.is_running {
+        return PAUSED_ICON;
+    }
+    phase_display(&status.phase).icon
+}
+
+fn status_color(status: &StatusUpdate, theme: &Theme) -> Color {
+    if status.is_overtime {
+        return theme.overtime_color;
+    }
+    if !status.is_running {
+        return theme.paused_color;
+    }
+    match status.phase.as_str() {
+        "short_break" => theme.short_break_color,
+        "long_break" => theme.long_break_color,
+        _ => theme.focus_color,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::PomodoroTimer;
+
+    #[test]
+    fn bars_agree_on_each_phase() {
+        let theme = Theme::nord();
+        let waybar = WaybarSettings::default();
+        let polybar = formatter("polybar", &theme, &waybar).unwrap();
+
+        let mut timer = PomodoroTimer::new(25, 5, 15, 1, false);
+        for phase in ["focus", "long_break"] {
+            if !timer.is_running() {
+                timer.toggle();
+            }
+            let status = StatusUpdate::from_timer(&timer, None);
+            assert_eq!(status.phase, phase);
+            let display = phase_display(phase);
+
+            let output = WaybarOutput::from_status(&status, &waybar);
+            assert_eq!(output.class[0], display.class);
+            assert!(output.tooltip.starts_with(display.label));
+            assert!(polybar.format(&status).contains(display.icon));
+            timer.skip();
+        }
+
+        assert_eq!(phase_display("nap"), phase_display(""));
+        assert_eq!(phase_display("nap").icon, PAUSED_ICON);
+    }
+}
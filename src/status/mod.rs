@@ -0,0 +1,469 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use directories::{BaseDirs, ProjectDirs};
+use chrono::{Local, SecondsFormat};
+use crate::config::settings::{IntegrationSettings, Settings};
+use crate::timer::PomodoroTimer;
+use crate::ui::Theme;
+use crate::log;
+
+pub mod format;
+pub mod sink;
+pub mod waybar;
+
+use format::JsonFormatter;
+use sink::{FileSink, StatusSink};
+
+/// Bumped whenever fields are removed or change meaning. New fields are
+/// added with defaults so older readers keep working.
+pub const STATUS_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusUpdate {
+    /// Version 1 files predate this field
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    pub phase: String,
+    /// `running`, `paused` or `overtime`
+    #[serde(default)]
+    pub state: String,
+    pub time_remaining: String,
+    pub session: String,
+    pub is_running: bool,
+    pub percentage_complete: u16,
+    #[serde(default)]
+    pub is_overtime: bool,
+    #[serde(default)]
+    pub today_focus: String,
+    #[serde(default)]
+    pub today_sessions: u32,
+    /// Seconds left in the phase; 0 in overtime
+    #[serde(default)]
+    pub remaining_seconds: u64,
+    /// Full length of the phase, including any extensions
+    #[serde(default)]
+    pub duration_seconds: u64,
+    #[serde(default)]
+    pub overtime_seconds: u64,
+    /// RFC 3339 time the phase runs out, only while running
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase_ends_at: Option<String>,
+    /// Completed focus sessions in the current cycle, starting at 0
+    #[serde(default)]
+    pub cycle_index: u32,
+    #[serde(default)]
+    pub cycles_before_long_break: u32,
+    #[serde(default)]
+    pub today_focus_seconds: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Unix time in milliseconds of the last change; never goes backwards
+    #[serde(default)]
+    pub updated_at: u64,
+    /// Process that wrote the file, for detecting a crashed instance
+    #[serde(default)]
+    pub pid: u32,
+}
+
+impl StatusUpdate {
+    pub fn from_timer(timer: &PomodoroTimer, profile: Option<&str>) -> Self {
+        let current = &timer.current_timer;
+        let state = if !timer.is_running() {
+            "paused"
+        } else if current.in_overtime() {
+            "overtime"
+        } else {
+            "running"
+        };
+
+        let phase_ends_at = (state == "running").then(|| {
+            let remaining = chrono::Duration::seconds(current.remaining.as_secs() as i64);
+            (Local::now() + remaining).to_rfc3339_opts(SecondsFormat::Secs, false)
+        });
+
+        Self {
+            schema_version: STATUS_SCHEMA_VERSION,
+            phase: timer.current_phase().as_str().to_string(),
+            state: state.to_string(),
+            time_remaining: timer.current_timer.format_time(),
+            session: timer.session_info(),
+            is_running: timer.is_running(),
+            percentage_complete: timer.current_timer.percentage_complete(),
+            is_overtime: timer.current_timer.in_overtime(),
+            today_focus: timer.stats.format_today_time(),
+            today_sessions: timer.stats.today_sessions,
+            remaining_seconds: current.remaining.as_secs(),
+            duration_seconds: current.duration.as_secs(),
+            overtime_seconds: current.overtime.as_secs(),
+            phase_ends_at,
+            cycle_index: timer.cycle_count,
+            cycles_before_long_break: timer.cycles_before_long_break,
+            today_focus_seconds: timer.stats.today_focus_time.as_secs(),
+            profile: profile.map(str::to_string),
+            updated_at: 0,
+            pid: std::process::id(),
+        }
+    }
+
+    /// Read the status file written by a running instance. Files left
+    /// behind by a process that no longer exists are ignored.
+    pub fn read(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        let status: Self = serde_json::from_str(&content).ok()?;
+        if status.is_stale() {
+            return None;
+        }
+        Some(status)
+    }
+
+    fn is_stale(&self) -> bool {
+        let proc_dir = Path::new("/proc");
+        // Files from older versions carry no pid, and without procfs we
+        // have no way to tell
+        self.pid != 0 && proc_dir.exists() && !proc_dir.join(self.pid.to_string()).exists()
+    }
+}
+
+/// Publishes the timer state to every configured `StatusSink`.
+pub struct StatusWriter {
+    profile: Option<String>,
+    sinks: Vec<SinkSlot>,
+    last_status: Option<StatusUpdate>,
+}
+
+struct SinkSlot {
+    sink: Box<dyn StatusSink>,
+    /// The latest status hasn't reached this sink yet
+    pending: bool,
+    /// A failure was reported and the sink hasn't recovered since, so it
+    /// isn't reported again on every retry
+    failing: bool,
+}
+
+impl SinkSlot {
+    fn new(sink: Box<dyn StatusSink>) -> Self {
+        Self { sink, pending: true, failing: false }
+    }
+}
+
+impl StatusWriter {
+    pub fn new(settings: &Settings, theme: &Theme) -> Result<Self, std::io::Error> {
+        let status_path = Self::status_path(&settings.integrations)?;
+
+        let mut sinks = Vec::new();
+        if settings.integrations.hyprland_status_bar {
+            sinks.push(SinkSlot::new(Box::new(FileSink::new(status_path, Box::new(JsonFormatter)))));
+        }
+
+        for output in &settings.integrations.status_outputs {
+            let Some(formatter) = format::formatter(&output.format, theme, &settings.waybar) else {
+                log::warning!("Unknown status output format: {}", output.format);
+                continue;
+            };
+            match expand_path(&output.path) {
+                Ok(path) => sinks.push(SinkSlot::new(Box::new(FileSink::new(path, formatter)))),
+                Err(e) => log::warning!("Skipping status output {}: {}", output.path, e),
+            }
+        }
+
+        Ok(Self {
+            profile: settings.timer.profile.clone(),
+            sinks,
+            last_status: None,
+        })
+    }
+
+    /// Location of status.json, from `status_path` or the cache directory.
+    pub fn status_path(settings: &IntegrationSettings) -> Result<PathBuf, std::io::Error> {
+        if let Some(path) = &settings.status_path {
+            match expand_path(path) {
+                Ok(path) => return Ok(path),
+                Err(e) => log::warning!("Ignoring status_path {}: {}", path, e),
+            }
+        }
+
+        let proj_dirs = ProjectDirs::from("", "", "devchron")
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Could not determine cache directory",
+                )
+            })?;
+
+        let cache_dir = proj_dirs.cache_dir();
+        fs::create_dir_all(cache_dir)?;
+
+        Ok(cache_dir.join("status.json"))
+    }
+
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    /// Adds a sink, which gets the current state on the next `update`.
+    pub fn add_sink(&mut self, sink: Box<dyn StatusSink>) {
+        self.sinks.push(SinkSlot::new(sink));
+    }
+
+    pub fn update(&mut self, timer: &PomodoroTimer) {
+        if self.sinks.is_empty() {
+            return;
+        }
+
+        let mut status = StatusUpdate::from_timer(timer, self.profile.as_deref());
+
+        let last_updated_at = self.last_status.as_ref().map_or(0, |last| last.updated_at);
+        status.updated_at = last_updated_at;
+        if self.last_status.as_ref() != Some(&status) {
+            status.updated_at = now_millis().max(last_updated_at + 1);
+            self.last_status = Some(status);
+            for slot in &mut self.sinks {
+                slot.pending = true;
+            }
+        }
+        let Some(status) = &self.last_status else {
+            return;
+        };
+
+        // Sinks that failed are retried on the next update, even if the
+        // status hasn't changed by then
+        for slot in self.sinks.iter_mut().filter(|slot| slot.pending) {
+            match slot.sink.write(status) {
+                Ok(()) => {
+                    if slot.failing {
+                        log::info!("Writing status to {} again", slot.sink.describe());
+                    }
+                    slot.pending = false;
+                    slot.failing = false;
+                }
+                Err(e) if !slot.failing => {
+                    log::error!("Failed to write status to {}: {}", slot.sink.describe(), e);
+                    slot.failing = true;
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+impl Drop for StatusWriter {
+    fn drop(&mut self) {
+        // Clean up status files on exit
+        for slot in &mut self.sinks {
+            slot.sink.clear();
+        }
+    }
+}
+
+fn default_schema_version() -> u32 { 1 }
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Expand a leading `~/` and any `$VAR` or `${VAR}` references. Fails if
+/// a variable is unset, rather than quietly writing somewhere else.
+fn expand_path(path: &str) -> Result<PathBuf, String> {
+    let path = match (path.strip_prefix("~/"), BaseDirs::new()) {
+        (Some(rest), Some(base)) => format!("{}/{}", base.home_dir().display(), rest),
+        _ => path.to_string(),
+    };
+
+    let mut expanded = String::new();
+    let mut rest = path.as_str();
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let (name, remainder) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => (braced, ""),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+
+        if name.is_empty() {
+            expanded.push('$');
+        } else {
+            let value = env::var(name)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| format!("${} is not set", name))?;
+            expanded.push_str(&value);
+        }
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+
+    Ok(PathBuf::from(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::io;
+    use std::rc::Rc;
+
+    #[derive(Default)]
+    struct Record {
+        attempts: usize,
+        written: Vec<u64>,
+    }
+
+    /// Records the remaining seconds it's given, failing the first
+    /// `failures` writes.
+    struct FakeSink {
+        record: Rc<RefCell<Record>>,
+        failures: usize,
+    }
+
+    impl FakeSink {
+        fn new(failures: usize) -> (Box<Self>, Rc<RefCell<Record>>) {
+            let record = Rc::new(RefCell::new(Record::default()));
+            (Box::new(Self { record: record.clone(), failures }), record)
+        }
+    }
+
+    impl StatusSink for FakeSink {
+        fn write(&mut self, status: &StatusUpdate) -> io::Result<()> {
+            let mut record = self.record.borrow_mut();
+            record.attempts += 1;
+            if record.attempts <= self.failures {
+                return Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only"));
+            }
+            record.written.push(status.remaining_seconds);
+            Ok(())
+        }
+
+        fn describe(&self) -> String {
+            "fake".to_string()
+        }
+    }
+
+    fn writer() -> StatusWriter {
+        StatusWriter { profile: None, sinks: Vec::new(), last_status: None }
+    }
+
+    #[test]
+    fn reads_version_1_files() {
+        let v1 = r#"{
+            "phase": "focus",
+            "time_remaining": "12:34",
+            "session": "2/4",
+            "is_running": true,
+            "percentage_complete": 50,
+            "is_overtime": false,
+            "today_focus": "0h 25m",
+            "today_sessions": 1,
+            "updated_at": 1700000000000,
+            "pid": 42
+        }"#;
+        let status: StatusUpdate = serde_json::from_str(v1).unwrap();
+        assert_eq!(status.schema_version, 1);
+        assert_eq!(status.phase, "focus");
+        assert_eq!(status.time_remaining, "12:34");
+        assert_eq!(status.state, "");
+        assert_eq!(status.remaining_seconds, 0);
+        assert_eq!(status.duration_seconds, 0);
+        assert_eq!(status.phase_ends_at, None);
+        assert_eq!(status.cycles_before_long_break, 0);
+        assert_eq!(status.profile, None);
+    }
+
+    #[test]
+    fn writes_version_2_and_reads_it_back() {
+        let mut timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        timer.toggle();
+        let status = StatusUpdate::from_timer(&timer, Some("deep"));
+
+        let json: serde_json::Value = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["schema_version"], 2);
+        assert_eq!(json["state"], "running");
+        assert_eq!(json["remaining_seconds"], 1500);
+        assert_eq!(json["profile"], "deep");
+        assert!(json["phase_ends_at"].is_string());
+
+        let read: StatusUpdate = serde_json::from_str(&json.to_string()).unwrap();
+        assert_eq!(read, status);
+    }
+
+    #[test]
+    fn failing_sink_is_retried_alone() {
+        let timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        let (working, working_record) = FakeSink::new(0);
+        let (failing, failing_record) = FakeSink::new(2);
+        let mut writer = writer();
+        writer.add_sink(working);
+        writer.add_sink(failing);
+
+        writer.update(&timer);
+        assert_eq!(working_record.borrow().written, [1500]);
+        assert!(failing_record.borrow().written.is_empty());
+        assert!(writer.sinks[1].failing);
+
+        // Same status: only the failed sink is tried again
+        writer.update(&timer);
+        writer.update(&timer);
+        assert_eq!(working_record.borrow().attempts, 1);
+        assert_eq!(failing_record.borrow().attempts, 3);
+        assert_eq!(failing_record.borrow().written, [1500]);
+        assert!(!writer.sinks[1].failing);
+
+        writer.update(&timer);
+        assert_eq!(failing_record.borrow().attempts, 3);
+    }
+
+    #[test]
+    fn failure_does_not_hold_back_other_sinks() {
+        let mut timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        let (broken, broken_record) = FakeSink::new(usize::MAX);
+        let (working, working_record) = FakeSink::new(0);
+        let mut writer = writer();
+        writer.add_sink(broken);
+        writer.add_sink(working);
+
+        writer.update(&timer);
+        timer.toggle();
+        timer.tick();
+        writer.update(&timer);
+        writer.update(&timer);
+
+        assert_eq!(working_record.borrow().written, [1500, 1499]);
+        assert_eq!(broken_record.borrow().attempts, 3);
+        assert!(writer.sinks[0].failing);
+        assert!(writer.sinks[0].pending);
+    }
+
+    #[test]
+    fn expands_variables() {
+        env::set_var("DEVCHRON_TEST_RUNTIME", "/run/user/1000");
+        assert_eq!(
+            expand_path("$DEVCHRON_TEST_RUNTIME/devchron.json"),
+            Ok(PathBuf::from("/run/user/1000/devchron.json"))
+        );
+        assert_eq!(
+            expand_path("${DEVCHRON_TEST_RUNTIME}_x/$/status"),
+            Ok(PathBuf::from("/run/user/1000_x/$/status"))
+        );
+    }
+
+    #[test]
+    fn unset_variables_are_an_error() {
+        env::remove_var("DEVCHRON_TEST_UNSET");
+        env::set_var("DEVCHRON_TEST_EMPTY", "");
+        assert!(expand_path("$DEVCHRON_TEST_UNSET/devchron.json").is_err());
+        assert!(expand_path("${DEVCHRON_TEST_EMPTY}/devchron.json").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::StatusUpdate;
+use super::format::StatusFormatter;
+
+/// A destination for status updates.
+///
+/// `StatusWriter` only calls `write` when the status has actually changed,
+/// or to retry after this sink's last write failed, so sinks don't need
+/// to deduplicate themselves.
+pub trait StatusSink {
+    fn write(&mut self, status: &StatusUpdate) -> std::io::Result<()>;
+
+    /// Called on shutdown so readers don't see a timer that is gone.
+    fn clear(&mut self) {}
+
+    /// Short description for error messages.
+    fn describe(&self) -> String;
+}
+
+/// Writes formatted status to a file, replacing it atomically.
+pub struct FileSink {
+    path: PathBuf,
+    formatter: Box<dyn StatusFormatter>,
+}
+
+impl FileSink {
+    pub fn new(path: PathBuf, formatter: Box<dyn StatusFormatter>) -> Self {
+        Self { path, formatter }
+    }
+}
+
+impl StatusSink for FileSink {
+    fn write(&mut self, status: &StatusUpdate) -> std::io::Result<()> {
+        write_atomic(&self.path, &self.formatter.format(status))
+    }
+
+    fn clear(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+/// Write via a temp file and rename so readers never see a partial file.
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a file path"))?;
+
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let tmp_path = dir.join(format!(".{}.tmp", file_name.to_string_lossy()));
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// What every `StatusSink` has to do, for each sink's tests to check.
+#[cfg(test)]
+pub(crate) mod contract {
+    use std::future::Future;
+    use std::time::Duration;
+    use super::StatusSink;
+    use crate::status::StatusUpdate;
+    use crate::timer::PomodoroTimer;
+
+    /// Write a status and then a newer one, checking readers see each in
+    /// turn, and that the sink can name itself. `read` returns the phase
+    /// and seconds remaining a reader currently sees. Sinks may publish
+    /// in the background, so reads are retried for a while.
+    pub async fn check<F, Fut>(sink: &mut dyn StatusSink, mut read: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Option<(String, u64)>>,
+    {
+        let mut timer = PomodoroTimer::new(25, 5, 15, 4, false);
+        sink.write(&StatusUpdate::from_timer(&timer, None)).unwrap();
+        wait_for(&mut read, "focus", 1500).await;
+
+        timer.skip();
+        sink.write(&StatusUpdate::from_timer(&timer, None)).unwrap();
+        wait_for(&mut read, "short_break", 300).await;
+
+        assert!(!sink.describe().is_empty());
+    }
+
+    async fn wait_for<F, Fut>(read: &mut F, phase: &str, remaining: u64)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Option<(String, u64)>>,
+    {
+        let mut seen = None;
+        for _ in 0..100 {
+            seen = read().await;
+            if seen.as_ref().is_some_and(|(p, r)| p == phase && *r == remaining) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("expected {} with {}s left, readers saw {:?}", phase, remaining, seen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::format::JsonFormatter;
+    use crate::timer::PomodoroTimer;
+
+    #[tokio::test]
+    async fn file_sink_meets_the_contract() {
+        let dir = std::env::temp_dir().join(format!("devchron-sink-contract-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("missing").join("status.json");
+        let mut sink = FileSink::new(path.clone(), Box::new(JsonFormatter));
+
+        contract::check(&mut sink, || {
+            let path = path.clone();
+            async move {
+                let status: StatusUpdate = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+                Some((status.phase, status.remaining_seconds))
+            }
+        })
+        .await;
+
+        // Only the status itself is left behind, no temp files
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn file_sink_writes_and_clears() {
+        let dir = std::env::temp_dir().join(format!("devchron-sink-{}", std::process::id()));
+        let path = dir.join("nested").join("status.json");
+        let mut sink = FileSink::new(path.clone(), Box::new(JsonFormatter));
+
+        let status = StatusUpdate::from_timer(&PomodoroTimer::new(25, 5, 15, 4, false), None);
+        sink.write(&status).unwrap();
+        let written: StatusUpdate = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, status);
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        sink.clear();
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn file_sink_reports_unwritable_paths() {
+        let mut sink = FileSink::new(PathBuf::from("/proc/devchron/status.json"), Box::new(JsonFormatter));
+        let status = StatusUpdate::from_timer(&PomodoroTimer::new(25, 5, 15, 4, false), None);
+        assert!(sink.write(&status).is_err());
+        assert_eq!(sink.describe(), "/proc/devchron/status.json");
+    }
+}
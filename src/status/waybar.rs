@@ -0,0 +1,101 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+use serde::Serialize;
+use crate::config::settings::WaybarSettings;
+use super::StatusUpdate;
+use super::format::phase_display;
+
+/// One line of Waybar's custom module JSON (`"return-type": "json"`).
+#[derive(Debug, Serialize)]
+pub struct WaybarOutput {
+    pub text: String,
+    pub tooltip: String,
+    pub class: Vec<String>,
+    pub percentage: u16,
+}
+
+impl WaybarOutput {
+    pub fn from_status(status: &StatusUpdate, settings: &WaybarSettings) -> Self {
+        let phase = phase_display(&status.phase);
+
+        let mut class = vec![phase.class.to_string()];
+        if status.is_overtime {
+            class.push("overtime".to_string());
+        }
+        if !status.is_running {
+            class.push("paused".to_string());
+        }
+
+        let format = if status.is_running {
+            &settings.format
+        } else {
+            &settings.format_paused
+        };
+
+        let text = format
+            .replace("{icon}", phase.icon)
+            .replace("{time}", &status.time_remaining)
+            .replace("{phase}", phase.label)
+            .replace("{session}", &status.session)
+            .replace("{percentage}", &status.percentage_complete.to_string())
+            .replace("{today}", &status.today_focus);
+
+        let tooltip = format!(
+            "{} {}\nSession {}\nToday: {} focused, {} sessions",
+            phase.label,
+            status.time_remaining,
+            status.session,
+            status.today_focus,
+            status.today_sessions,
+        );
+
+        Self {
+            text,
+            tooltip,
+            class,
+            percentage: status.percentage_complete,
+        }
+    }
+
+    pub fn inactive(settings: &WaybarSettings) -> Self {
+        Self {
+            text: settings.format_inactive.clone(),
+            tooltip: "DevChron not running".to_string(),
+            class: vec!["inactive".to_string()],
+            percentage: 0,
+        }
+    }
+}
+
+/// Stream Waybar JSON to stdout, one line each time the status changes.
+///
+/// Meant for Waybar's continuous `exec` mode, so it runs until Waybar
+/// closes the pipe.
+pub async fn run(settings: &WaybarSettings, status_path: &Path) -> crate::error::Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_millis(settings.interval_ms.max(50)));
+    let mut last_line = String::new();
+    let mut stdout = io::stdout();
+
+    loop {
+        interval.tick().await;
+
+        let output = match StatusUpdate::read(status_path) {
+            Some(status) => WaybarOutput::from_status(&status, settings),
+            None => WaybarOutput::inactive(settings),
+        };
+
+        let Ok(line) = serde_json::to_string(&output) else {
+            continue;
+        };
+        if line == last_line {
+            continue;
+        }
+
+        // A closed pipe means Waybar went away
+        if writeln!(stdout, "{}", line).and_then(|_| stdout.flush()).is_err() {
+            return Ok(());
+        }
+        last_line = line;
+    }
+}
@@ -2,5 +2,5 @@ pub mod state;
 pub mod session;
 pub mod pomodoro;
 
-pub use state::TimerPhase;
+pub use state::{TimerPhase, TimerState};
 pub use pomodoro::PomodoroTimer;
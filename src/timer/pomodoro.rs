@@ -10,6 +10,7 @@ pub struct PomodoroTimer {
     pub focus_duration: Duration,
     pub short_break_duration: Duration,
     pub long_break_duration: Duration,
+    pub overtime_enabled: bool,
     pub stats: SessionStats,
 }
 
@@ -19,15 +20,18 @@ impl PomodoroTimer {
         short_break_mins: u64,
         long_break_mins: u64,
         cycles_before_long_break: u32,
+        overtime_enabled: bool,
     ) -> Self {
         let focus_duration = Duration::from_secs(focus_mins * 60);
         Self {
-            current_timer: Timer::new(TimerPhase::Focus, focus_duration),
+            current_timer: Timer::new(TimerPhase::Focus, focus_duration)
+                .with_overtime(overtime_enabled),
             cycle_count: 0,
             cycles_before_long_break,
             focus_duration,
             short_break_duration: Duration::from_secs(short_break_mins * 60),
             long_break_duration: Duration::from_secs(long_break_mins * 60),
+            overtime_enabled,
             stats: SessionStats::default(),
         }
     }
@@ -48,26 +52,25 @@ impl PomodoroTimer {
         self.current_timer.reset();
     }
 
+    pub fn adjust(&mut self, secs: i64) -> i64 {
+        self.current_timer.adjust(secs)
+    }
+
     pub fn skip(&mut self) {
         self.advance_to_next_phase();
     }
 
-    pub fn tick(&mut self) -> bool {
-        let completed = self.current_timer.tick();
-        if completed {
-            self.advance_to_next_phase();
-            true
-        } else {
-            false
-        }
+    /// Advance the timer by one second, returning the phase that just ran
+    /// out. The timer stays on that phase, completed or in overtime, until
+    /// `skip` moves on, so whoever reacts to the end still sees it.
+    pub fn tick(&mut self) -> Option<TimerPhase> {
+        self.current_timer.tick().then_some(self.current_timer.phase)
     }
 
     fn advance_to_next_phase(&mut self) {
         match self.current_timer.phase {
             TimerPhase::Focus => {
-                self.stats.complete_focus_session(
-                    self.focus_duration - self.current_timer.remaining
-                );
+                self.stats.complete_focus_session(self.current_timer.elapsed());
                 self.cycle_count += 1;
                 
                 let next_phase = if self.cycle_count >= self.cycles_before_long_break {
@@ -86,14 +89,41 @@ impl PomodoroTimer {
                 self.current_timer = Timer::new(next_phase, duration);
             }
             TimerPhase::ShortBreak | TimerPhase::LongBreak => {
-                self.stats.complete_break_session(
-                    self.current_timer.duration - self.current_timer.remaining
-                );
-                self.current_timer = Timer::new(TimerPhase::Focus, self.focus_duration);
+                self.stats.complete_break_session(self.current_timer.elapsed());
+                self.current_timer = Timer::new(TimerPhase::Focus, self.focus_duration)
+                    .with_overtime(self.overtime_enabled);
             }
         }
     }
 
+    /// Switches to new phase lengths. They apply from the next phase on,
+    /// or right away if the current one hasn't been started yet.
+    pub fn set_durations(
+        &mut self,
+        focus_mins: u64,
+        short_break_mins: u64,
+        long_break_mins: u64,
+        cycles_before_long_break: u32,
+        overtime_enabled: bool,
+    ) {
+        self.focus_duration = Duration::from_secs(focus_mins * 60);
+        self.short_break_duration = Duration::from_secs(short_break_mins * 60);
+        self.long_break_duration = Duration::from_secs(long_break_mins * 60);
+        self.cycles_before_long_break = cycles_before_long_break;
+        self.overtime_enabled = overtime_enabled;
+
+        let current = &self.current_timer;
+        let untouched = current.state == TimerState::Paused && current.remaining == current.duration;
+        if untouched {
+            let phase = current.phase;
+            self.current_timer = match phase {
+                TimerPhase::Focus => Timer::new(phase, self.focus_duration).with_overtime(overtime_enabled),
+                TimerPhase::ShortBreak => Timer::new(phase, self.short_break_duration),
+                TimerPhase::LongBreak => Timer::new(phase, self.long_break_duration),
+            };
+        }
+    }
+
     pub fn current_phase(&self) -> TimerPhase {
         self.current_timer.phase
     }
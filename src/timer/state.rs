@@ -1,5 +1,8 @@
 use std::time::Duration;
 
+/// Largest change a single `adjust` makes, either way.
+const MAX_ADJUST_SECS: i64 = 24 * 60 * 60;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimerState {
     Running,
@@ -14,7 +17,19 @@ pub enum TimerPhase {
     LongBreak,
 }
 
-impl TimerPhase {}
+impl TimerPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimerPhase::Focus => "focus",
+            TimerPhase::ShortBreak => "short_break",
+            TimerPhase::LongBreak => "long_break",
+        }
+    }
+
+    pub fn is_break(&self) -> bool {
+        matches!(self, TimerPhase::ShortBreak | TimerPhase::LongBreak)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Timer {
@@ -22,6 +37,14 @@ pub struct Timer {
     pub state: TimerState,
     pub duration: Duration,
     pub remaining: Duration,
+    /// Keep counting past zero instead of completing
+    pub overtime_enabled: bool,
+    pub overtime: Duration,
+    /// Whether the clock has reached zero in this phase. Completion is
+    /// announced once, even if extending puts time back on the clock.
+    pub ran_out: bool,
+    /// Time counted since the timer was last started or resumed
+    pub running_for: Duration,
 }
 
 impl Timer {
@@ -31,11 +54,21 @@ impl Timer {
             state: TimerState::Paused,
             duration,
             remaining: duration,
+            overtime_enabled: false,
+            overtime: Duration::ZERO,
+            ran_out: false,
+            running_for: Duration::ZERO,
         }
     }
 
+    pub fn with_overtime(mut self, enabled: bool) -> Self {
+        self.overtime_enabled = enabled;
+        self
+    }
+
     pub fn start(&mut self) {
         self.state = TimerState::Running;
+        self.running_for = Duration::ZERO;
     }
 
     pub fn pause(&mut self) {
@@ -44,24 +77,82 @@ impl Timer {
 
     pub fn reset(&mut self) {
         self.remaining = self.duration;
+        self.overtime = Duration::ZERO;
+        self.ran_out = false;
         self.state = TimerState::Paused;
     }
 
+    /// Count down one second. Returns true the first time the phase runs
+    /// out.
     pub fn tick(&mut self) -> bool {
         if self.state != TimerState::Running {
             return false;
         }
+        self.running_for += Duration::from_secs(1);
 
         if self.remaining > Duration::from_secs(1) {
             self.remaining -= Duration::from_secs(1);
             false
+        } else if self.overtime_enabled && self.ran_out {
+            // Completion was already reported, keep counting past zero
+            self.overtime += Duration::from_secs(1);
+            false
         } else {
             self.remaining = Duration::ZERO;
-            self.state = TimerState::Completed;
-            true
+            if !self.overtime_enabled {
+                self.state = TimerState::Completed;
+            }
+            !std::mem::replace(&mut self.ran_out, true)
         }
     }
 
+    /// Lengthen (positive) or shorten (negative) the phase by `secs`,
+    /// returning the adjustment actually applied. Shortening never ends
+    /// the phase outright; at least one second is left on the clock, and
+    /// no more than a day is added or taken at once.
+    pub fn adjust(&mut self, secs: i64) -> i64 {
+        let secs = secs.clamp(-MAX_ADJUST_SECS, MAX_ADJUST_SECS);
+        let delta = Duration::from_secs(secs.unsigned_abs());
+
+        if secs >= 0 {
+            // Extra time first pays back any overtime already run up
+            let absorbed = delta.min(self.overtime);
+            self.overtime -= absorbed;
+            self.remaining = self.remaining.saturating_add(delta - absorbed);
+            self.duration = self.duration.saturating_add(delta);
+            return secs;
+        }
+
+        let applied = if self.remaining.is_zero() {
+            let applied = delta.min(self.duration.saturating_sub(Duration::from_secs(1)));
+            self.overtime = self.overtime.saturating_add(applied);
+            applied
+        } else {
+            let applied = delta.min(self.remaining.saturating_sub(Duration::from_secs(1)));
+            self.remaining -= applied;
+            applied
+        };
+        self.duration -= applied;
+        -(applied.as_secs() as i64)
+    }
+
+    /// Wind the clock back by `amount` without changing the phase length,
+    /// un-counting time that should not have been credited.
+    pub fn rewind(&mut self, amount: Duration) {
+        let from_overtime = amount.min(self.overtime);
+        self.overtime -= from_overtime;
+        self.remaining = (self.remaining + (amount - from_overtime)).min(self.duration);
+    }
+
+    pub fn in_overtime(&self) -> bool {
+        self.overtime_enabled && self.remaining.is_zero()
+    }
+
+    /// Time actually spent in this phase, including overtime.
+    pub fn elapsed(&self) -> Duration {
+        self.duration - self.remaining + self.overtime
+    }
+
     pub fn is_running(&self) -> bool {
         self.state == TimerState::Running
     }
@@ -73,9 +164,91 @@ impl Timer {
     }
 
     pub fn format_time(&self) -> String {
+        if !self.overtime.is_zero() {
+            let total_secs = self.overtime.as_secs();
+            return format!("+{:02}:{:02}", total_secs / 60, total_secs % 60);
+        }
+
         let total_secs = self.remaining.as_secs();
         let mins = total_secs / 60;
         let secs = total_secs % 60;
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_out(timer: &mut Timer) -> usize {
+        (0..timer.remaining.as_secs()).filter(|_| timer.tick()).count()
+    }
+
+    #[test]
+    fn extending_after_overtime_does_not_complete_again() {
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::from_secs(3)).with_overtime(true);
+        timer.start();
+        assert_eq!(run_out(&mut timer), 1);
+        assert!(timer.in_overtime());
+
+        timer.tick();
+        timer.adjust(60);
+        assert!(!timer.in_overtime());
+        assert_eq!(run_out(&mut timer), 0);
+        assert!(timer.ran_out);
+    }
+
+    #[test]
+    fn zero_length_phase_finishes() {
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::ZERO);
+        timer.start();
+        assert!(timer.tick());
+        assert_eq!(timer.state, TimerState::Completed);
+
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::ZERO).with_overtime(true);
+        timer.start();
+        assert!(timer.tick());
+        assert!(timer.in_overtime());
+        assert!(!timer.tick());
+        assert_eq!(timer.overtime, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn adjust_is_capped_at_a_day() {
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::from_secs(60));
+        assert_eq!(timer.adjust(i64::MAX), MAX_ADJUST_SECS);
+        assert_eq!(timer.remaining, Duration::from_secs(60 + MAX_ADJUST_SECS as u64));
+
+        assert_eq!(timer.adjust(i64::MIN), -MAX_ADJUST_SECS);
+        assert_eq!(timer.remaining, Duration::from_secs(60));
+        assert_eq!(timer.adjust(-3600), -59);
+        assert_eq!(timer.remaining, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn running_for_restarts_on_resume() {
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::from_secs(60));
+        timer.start();
+        timer.tick();
+        timer.tick();
+        assert_eq!(timer.running_for, Duration::from_secs(2));
+
+        timer.pause();
+        timer.tick();
+        timer.start();
+        timer.tick();
+        assert_eq!(timer.running_for, Duration::from_secs(1));
+        assert_eq!(timer.elapsed(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn reset_completes_again() {
+        let mut timer = Timer::new(TimerPhase::Focus, Duration::from_secs(2));
+        timer.start();
+        assert_eq!(run_out(&mut timer), 1);
+
+        timer.reset();
+        timer.start();
+        assert_eq!(run_out(&mut timer), 1);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::config::settings::ClockStyle;
+
+/// Largest scale tried before giving up on filling the area.
+const MAX_SCALE: usize = 8;
+
+/// 3x5 bitmaps for the block and braille styles, `#` marks a pixel.
+fn bitmap(c: char) -> Option<[&'static str; 5]> {
+    Some(match c {
+        '0' => ["###", "# #", "# #", "# #", "###"],
+        '1' => ["  #", "  #", "  #", "  #", "  #"],
+        '2' => ["###", "  #", "###", "#  ", "###"],
+        '3' => ["###", "  #", "###", "  #", "###"],
+        '4' => ["# #", "# #", "###", "  #", "  #"],
+        '5' => ["###", "#  ", "###", "  #", "###"],
+        '6' => ["###", "#  ", "###", "# #", "###"],
+        '7' => ["###", "  #", "  #", "  #", "  #"],
+        '8' => ["###", "# #", "###", "# #", "###"],
+        '9' => ["###", "# #", "###", "  #", "###"],
+        ':' => [" ", "#", " ", "#", " "],
+        '+' => ["   ", " # ", "###", " # ", "   "],
+        _ => return None,
+    })
+}
+
+/// Renders `text` (digits, `:` and `+`) in large characters that fit in
+/// `width` x `height` cells, as big as possible. Returns `None` for the
+/// text style or when even the smallest size doesn't fit.
+pub fn big_lines(style: ClockStyle, text: &str, width: u16, height: u16) -> Option<Vec<String>> {
+    let fits = |lines: &Vec<String>| {
+        lines.len() <= height as usize
+            && lines.iter().all(|line| line.chars().count() <= width as usize)
+    };
+
+    if style == ClockStyle::Figlet {
+        return figlet(text).filter(fits);
+    }
+
+    let mut best = None;
+    for scale in 1..=MAX_SCALE {
+        let lines = match style {
+            ClockStyle::Block => block(text, scale)?,
+            ClockStyle::Braille => braille(text, scale)?,
+            ClockStyle::SevenSegment => seven_segment(text, scale)?,
+            ClockStyle::Text | ClockStyle::Figlet => return None,
+        };
+        if !fits(&lines) {
+            break;
+        }
+        best = Some(lines);
+    }
+    best
+}
+
+/// The bitmap font laid out as one pixel grid, one column between glyphs.
+fn canvas(text: &str) -> Option<Vec<Vec<bool>>> {
+    let mut rows = vec![Vec::new(); 5];
+    for (i, c) in text.chars().enumerate() {
+        let glyph = bitmap(c)?;
+        for (row, pixels) in rows.iter_mut().zip(glyph) {
+            if i > 0 {
+                row.push(false);
+            }
+            row.extend(pixels.chars().map(|p| p == '#'));
+        }
+    }
+    Some(rows)
+}
+
+fn block(text: &str, scale: usize) -> Option<Vec<String>> {
+    let mut lines = Vec::new();
+    for row in canvas(text)? {
+        // Cells are about twice as tall as wide, so pixels are two columns
+        let line: String = row
+            .iter()
+            .flat_map(|&on| std::iter::repeat_n(if on { '█' } else { ' ' }, 2 * scale))
+            .collect();
+        lines.extend(std::iter::repeat_n(line, scale));
+    }
+    Some(lines)
+}
+
+fn braille(text: &str, scale: usize) -> Option<Vec<String>> {
+    let pixels = canvas(text)?;
+    let dot = |x: usize, y: usize| {
+        pixels
+            .get(y / scale)
+            .and_then(|row| row.get(x / scale))
+            .copied()
+            .unwrap_or(false)
+    };
+
+    let dots_wide = pixels[0].len() * scale;
+    let dots_high = pixels.len() * scale;
+
+    // Dot bits of a braille cell, indexed by [x][y]
+    const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    let mut lines = Vec::new();
+    for cell_y in (0..dots_high).step_by(4) {
+        let mut line = String::new();
+        for cell_x in (0..dots_wide).step_by(2) {
+            let mut bits = 0;
+            for (dx, column) in BITS.iter().enumerate() {
+                for (dy, bit) in column.iter().enumerate() {
+                    if dot(cell_x + dx, cell_y + dy) {
+                        bits |= bit;
+                    }
+                }
+            }
+            line.push(if bits == 0 { ' ' } else { char::from_u32(0x2800 + bits)? });
+        }
+        lines.push(line);
+    }
+    Some(lines)
+}
+
+/// Segments a-g of a seven-segment display, clockwise from the top with
+/// g in the middle.
+fn segments(c: char) -> Option<[bool; 7]> {
+    let lit = match c {
+        '0' => "abcdef",
+        '1' => "bc",
+        '2' => "abdeg",
+        '3' => "abcdg",
+        '4' => "bcfg",
+        '5' => "acdfg",
+        '6' => "acdefg",
+        '7' => "abc",
+        '8' => "abcdefg",
+        '9' => "abcdfg",
+        _ => return None,
+    };
+    let mut segments = [false; 7];
+    for segment in lit.bytes() {
+        segments[(segment - b'a') as usize] = true;
+    }
+    Some(segments)
+}
+
+fn seven_segment(text: &str, scale: usize) -> Option<Vec<String>> {
+    // Horizontal segments are `len` columns, vertical ones `span` rows
+    let span = scale;
+    let len = 2 * scale;
+    let height = 2 * span + 3;
+    let middle = span + 1;
+    // Centers of the upper and lower halves, for the colon and plus
+    let upper = 1 + (span - 1) / 2;
+    let lower = upper + span + 1;
+
+    let mut lines = vec![String::new(); height];
+    for (i, c) in text.chars().enumerate() {
+        let glyph: Vec<String> = match c {
+            ':' => (0..height)
+                .map(|y| if y == upper || y == lower { "●" } else { " " }.to_string())
+                .collect(),
+            '+' => (0..height)
+                .map(|y| {
+                    let arm = if y == middle {
+                        "━".repeat(len)
+                    } else if (upper..=lower).contains(&y) {
+                        format!("{:^width$}", "┃", width = len)
+                    } else {
+                        " ".repeat(len)
+                    };
+                    format!(" {} ", arm)
+                })
+                .collect(),
+            _ => {
+                let [a, b, c, d, e, f, g] = segments(c)?;
+                let bar = |on: bool| format!(" {} ", if on { "━".repeat(len) } else { " ".repeat(len) });
+                let sides = |left: bool, right: bool| {
+                    format!(
+                        "{}{}{}",
+                        if left { "┃" } else { " " },
+                        " ".repeat(len),
+                        if right { "┃" } else { " " }
+                    )
+                };
+                let mut glyph = vec![bar(a)];
+                glyph.extend(std::iter::repeat_n(sides(f, b), span));
+                glyph.push(bar(g));
+                glyph.extend(std::iter::repeat_n(sides(e, c), span));
+                glyph.push(bar(d));
+                glyph
+            }
+        };
+
+        for (line, part) in lines.iter_mut().zip(glyph) {
+            if i > 0 {
+                line.push(' ');
+            }
+            line.push_str(&part);
+        }
+    }
+    Some(lines)
+}
+
+/// The figlet "standard" font. It has a single size.
+fn figlet_glyph(c: char) -> Option<[&'static str; 5]> {
+    Some(match c {
+        '0' => ["  ___  ", " / _ \\ ", "| | | |", "| |_| |", " \\___/ "],
+        '1' => [" _ ", "/ |", "| |", "| |", "|_|"],
+        '2' => [" ____  ", "|___ \\ ", "  __) |", " / __/ ", "|_____|"],
+        '3' => [" _____ ", "|___ / ", "  |_ \\ ", " ___) |", "|____/ "],
+        '4' => [" _  _   ", "| || |  ", "| || |_ ", "|__   _|", "   |_|  "],
+        '5' => [" ____  ", "| ___| ", "|___ \\ ", " ___) |", "|____/ "],
+        '6' => ["  __   ", " / /_  ", "| '_ \\ ", "| (_) |", " \\___/ "],
+        '7' => [" _____ ", "|___  |", "   / / ", "  / /  ", " /_/   "],
+        '8' => ["  ___  ", " ( _ ) ", " / _ \\ ", "| (_) |", " \\___/ "],
+        '9' => ["  ___  ", " / _ \\ ", "| (_) |", " \\__, |", "   /_/ "],
+        ':' => ["   ", " _ ", "(_)", " _ ", "(_)"],
+        '+' => ["       ", "   _   ", " _| |_ ", "|_   _|", "  |_|  "],
+        _ => return None,
+    })
+}
+
+fn figlet(text: &str) -> Option<Vec<String>> {
+    let mut lines = vec![String::new(); 5];
+    for c in text.chars() {
+        for (line, part) in lines.iter_mut().zip(figlet_glyph(c)?) {
+            line.push_str(part);
+        }
+    }
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn width(lines: &[String]) -> usize {
+        lines.iter().map(|line| line.chars().count()).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn grows_to_fill_the_area() {
+        // 17 pixels wide and 5 high, each pixel two columns by one row
+        let lines = big_lines(ClockStyle::Block, "25:00", 80, 12).unwrap();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(width(&lines), 68);
+
+        for style in [ClockStyle::Braille, ClockStyle::SevenSegment, ClockStyle::Figlet] {
+            let lines = big_lines(style, "25:00", 80, 12).unwrap();
+            assert!(lines.len() <= 12 && width(&lines) <= 80, "{:?} overflows", style);
+        }
+    }
+
+    #[test]
+    fn too_narrow_falls_back_to_plain_digits() {
+        for style in [ClockStyle::Block, ClockStyle::Braille, ClockStyle::SevenSegment, ClockStyle::Figlet] {
+            assert_eq!(big_lines(style, "25:00", 8, 12), None, "{:?}", style);
+            assert_eq!(big_lines(style, "25:00", 80, 1), None, "{:?}", style);
+        }
+        assert_eq!(big_lines(ClockStyle::Text, "25:00", 200, 50), None);
+    }
+
+    #[test]
+    fn draws_overtime_with_a_plus() {
+        let lines = big_lines(ClockStyle::Block, "+01:30", 42, 9).unwrap();
+        assert_eq!(lines.len(), 5);
+        // The plus is the first glyph, its bar across the middle row
+        assert!(lines[2].starts_with("██████ "));
+        assert!(lines[0].starts_with("       "));
+
+        for style in [ClockStyle::Braille, ClockStyle::SevenSegment, ClockStyle::Figlet] {
+            assert!(big_lines(style, "+01:30", 120, 20).is_some(), "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn unknown_characters_are_not_drawn() {
+        assert_eq!(big_lines(ClockStyle::Block, "--:--", 200, 50), None);
+    }
+}
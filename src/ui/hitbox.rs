@@ -0,0 +1,45 @@
+use ratatui::layout::Rect;
+use crate::events::Action;
+
+/// What a mouse event over a rendered region refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A control that performs an action when clicked
+    Button(Action),
+    /// The clock, where scrolling adjusts the time
+    Timer,
+    /// The progress bar; `Rect` spans the track from 0% to 100%
+    ProgressBar,
+    /// The help popup, where scrolling moves through the list
+    Help,
+}
+
+/// Regions recorded while rendering a frame, for hit-testing the mouse
+/// against what is actually on screen.
+#[derive(Debug, Default)]
+pub struct Hitboxes {
+    regions: Vec<(Rect, Target)>,
+}
+
+impl Hitboxes {
+    pub fn add(&mut self, area: Rect, target: Target) {
+        if area.width > 0 && area.height > 0 {
+            self.regions.push((area, target));
+        }
+    }
+
+    /// The topmost region under the cell, with its area. Later regions are
+    /// drawn over earlier ones, so they win.
+    pub fn at(&self, column: u16, row: u16) -> Option<(Rect, Target)> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                column >= area.x
+                    && column < area.x + area.width
+                    && row >= area.y
+                    && row < area.y + area.height
+            })
+            .copied()
+    }
+}
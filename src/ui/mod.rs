@@ -1,5 +1,9 @@
 pub mod theme;
 pub mod render;
+pub mod clock;
+pub mod hitbox;
+pub mod preview;
+pub mod toast;
 
 pub use theme::Theme;
 pub use render::render;
@@ -0,0 +1,85 @@
+use crossterm::style::{Color as CColor, Stylize};
+use ratatui::style::Color;
+use unicode_width::UnicodeWidthStr;
+use crate::i18n::Locale;
+use super::theme::{self, Theme, BUILTIN_THEMES};
+
+pub fn print_list(current: &str) {
+    let marker = |name: &str| if name.eq_ignore_ascii_case(current) { "*" } else { " " };
+
+    println!("Built-in:");
+    for name in BUILTIN_THEMES {
+        println!(" {} {}", marker(name), name);
+    }
+
+    let user_themes = theme::user_themes();
+    if let Some(dir) = theme::themes_dir() {
+        println!("\n{}:", dir.display());
+    }
+    if user_themes.is_empty() {
+        println!("   (none)");
+    }
+    for name in &user_themes {
+        println!(" {} {}", marker(name), name);
+    }
+}
+
+pub fn print_preview(name: &str, theme: &Theme, locale: &Locale) {
+    println!("{}\n", name.bold());
+
+    let colors = [
+        ("focus_color", Some(theme.focus_color)),
+        ("short_break_color", Some(theme.short_break_color)),
+        ("long_break_color", Some(theme.long_break_color)),
+        ("paused_color", Some(theme.paused_color)),
+        ("overtime_color", Some(theme.overtime_color)),
+        ("text", Some(theme.text)),
+        ("border", Some(theme.border)),
+        ("background", Some(theme.background)),
+        ("header", Some(theme.header)),
+        ("progress_filled", theme.progress_filled),
+        ("progress_empty", theme.progress_empty),
+        ("info_color", Some(theme.info_color)),
+        ("warning_color", Some(theme.warning_color)),
+        ("error_color", Some(theme.error_color)),
+    ];
+    for (field, color) in colors {
+        match color {
+            Some(color) => println!("  {}  {:<18} {}", swatch(color), field, describe(color)),
+            None => println!("        {:<18} phase color", field),
+        }
+    }
+
+    // A miniature of the timer in each phase
+    println!();
+    let background = CColor::from(theme.background);
+    let phases = [
+        (locale.label("phase.focus"), "25:00", theme.focus_color),
+        (locale.label("phase.short_break"), "05:00", theme.short_break_color),
+        (locale.label("phase.long_break"), "15:00", theme.long_break_color),
+        (locale.label("phase.paused"), "12:34", theme.paused_color),
+        (locale.label("phase.overtime"), "+01:30", theme.overtime_color),
+    ];
+    let width = phases.iter().map(|(label, ..)| label.width()).max().unwrap_or(0);
+    for (label, time, color) in phases {
+        let label = format!("{}{} {:>6}", label, " ".repeat(width - label.width()), time);
+        println!(
+            "  {}{}{}",
+            "│ ".with(CColor::from(theme.border)).on(background),
+            label.with(CColor::from(color)).on(background).bold(),
+            " │".with(CColor::from(theme.border)).on(background),
+        );
+    }
+}
+
+fn swatch(color: Color) -> String {
+    "    ".on(CColor::from(color)).to_string()
+}
+
+fn describe(color: Color) -> String {
+    match color {
+        Color::Rgb(..) => theme::to_hex(color),
+        Color::Reset => "terminal default".to_string(),
+        _ => color.to_string().to_lowercase(),
+    }
+}
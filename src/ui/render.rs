@@ -2,19 +2,86 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, BorderType, Paragraph},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, BorderType, Clear, Padding, Paragraph,
+    },
     Frame,
 };
+use crate::app::App;
+use crate::events::Action;
+use crate::events::keymap::{Binding, Keymap};
+use crate::i18n::Locale;
+use crate::log::Level;
 use crate::timer::{PomodoroTimer, TimerPhase};
+use unicode_width::UnicodeWidthStr;
+use super::clock;
+use super::hitbox::{Hitboxes, Target};
 use super::theme::Theme;
+use super::toast::Toast;
 
-pub fn render(f: &mut Frame, timer: &PomodoroTimer, theme: &Theme, show_help: bool) {
+/// How much of the interface fits in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayoutMode {
+    /// Header, timer and the statistics/controls footer
+    Full,
+    /// Timer and progress bar inside a titled border
+    Compact,
+    /// A single status line
+    Minimal,
+}
+
+impl LayoutMode {
+    fn for_size(size: Rect) -> Self {
+        if size.height < 7 || size.width < 30 {
+            LayoutMode::Minimal
+        } else if size.height < 22 || size.width < 64 {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Full
+        }
+    }
+}
+
+/// Draws the frame and returns the regions the mouse can interact with.
+pub fn render(f: &mut Frame, app: &App) -> Hitboxes {
     let size = f.size();
+    let timer = &app.timer;
+    let theme = &app.theme;
+    let mut hitboxes = Hitboxes::default();
     
-    if show_help {
-        render_help(f, size, theme);
-        return;
+    f.render_widget(Block::default().style(Style::default().bg(theme.background)), size);
+    
+    if app.break_locked() {
+        render_break_overlay(f, size, app);
+        render_toasts(f, size, app);
+        return hitboxes;
+    }
+    
+    match LayoutMode::for_size(size) {
+        LayoutMode::Full => render_full(f, size, app, &mut hitboxes),
+        LayoutMode::Compact => render_compact(f, size, app, &mut hitboxes),
+        LayoutMode::Minimal => {
+            render_minimal(f, size, timer, theme, &app.locale);
+            hitboxes.add(size, Target::Timer);
+        }
+    }
+    
+    if app.show_help {
+        render_help(f, size, app, &mut hitboxes);
     }
+    if app.palette.is_open() {
+        render_palette(f, size, app);
+        // Typing goes to the palette; the mouse waits until it closes
+        hitboxes = Hitboxes::default();
+    }
+    render_toasts(f, size, app);
+    hitboxes
+}
+
+fn render_full(f: &mut Frame, size: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let timer = &app.timer;
+    let theme = &app.theme;
     
     // Main layout with custom borders
     let main_block = Block::default()
@@ -29,62 +96,167 @@ pub fn render(f: &mut Frame, timer: &PomodoroTimer, theme: &Theme, show_help: bo
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header with phase and session
+            Constraint::Length(2),  // Header with phase and session
             Constraint::Min(10),    // Main timer area
             Constraint::Length(7),  // Stats and controls
         ])
         .split(inner);
     
-    render_header(f, chunks[0], timer, theme);
-    render_timer(f, chunks[1], timer, theme);
-    render_footer(f, chunks[2], timer, theme);
+    render_header(f, chunks[0], timer, theme, &app.locale);
+    render_timer(f, chunks[1], app, hitboxes);
+    render_footer(f, chunks[2], app, hitboxes);
 }
 
-fn render_header(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme) {
+fn render_compact(f: &mut Frame, size: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let timer = &app.timer;
+    let theme = &app.theme;
     let phase_color = get_phase_color(timer.current_timer.phase, theme);
     
-    let (phase_kanji, phase_name) = match timer.current_timer.phase {
-        TimerPhase::Focus => ("焦 点", "FOCUS"),
-        TimerPhase::ShortBreak => ("小休憩", "SHORT BREAK"),
-        TimerPhase::LongBreak => ("長休憩", "LONG BREAK"),
-    };
+    // The header shrinks into the border title
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(theme.border))
+        .title(Line::from(vec![
+            Span::styled(
+                format!(" {} ", app.locale.label(phase_key(timer.current_timer.phase))),
+                Style::default().fg(phase_color).add_modifier(Modifier::BOLD)
+            ),
+        ]))
+        .title(
+            Line::from(Span::styled(format!(" {} ", session_text(timer)), Style::default().fg(phase_color)))
+                .alignment(Alignment::Right)
+        );
     
-    let session_text = format!("{:02}/{:02}", 
-        timer.cycle_count + 1, 
-        timer.cycles_before_long_break
-    );
+    let inner = block.inner(size);
+    f.render_widget(block, size);
+    render_timer(f, inner, app, hitboxes);
+}
+
+/// One line: phase, time, progress bar and session, dropping the bar and
+/// then the labels as the width shrinks.
+fn render_minimal(f: &mut Frame, size: Rect, timer: &PomodoroTimer, theme: &Theme, locale: &Locale) {
+    let color = timer_color(timer, theme);
+    let time = timer.current_timer.format_time();
+    let session = session_text(timer);
+    let width = size.width as usize;
+    
+    let phase = locale.text(phase_key(timer.current_timer.phase));
+    let labelled = phase.width() + time.width() + session.width() + 4;
+    
+    let mut spans = Vec::new();
+    if width >= labelled {
+        spans.push(Span::styled(phase, Style::default().fg(get_phase_color(timer.current_timer.phase, theme)).add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw("  "));
+    }
+    spans.push(Span::styled(time, Style::default().fg(color).add_modifier(Modifier::BOLD)));
+    
+    // Whatever is left between the time and the session goes to the bar
+    let bar_width = width.saturating_sub(labelled + 2);
+    if width >= labelled && bar_width >= 5 {
+        let filled = bar_width * timer.current_timer.percentage_complete() as usize / 100;
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled("━".repeat(filled), Style::default().fg(theme.progress_filled.unwrap_or(color))));
+        spans.push(Span::styled("─".repeat(bar_width - filled), Style::default().fg(theme.progress_empty.unwrap_or(color))));
+        spans.push(Span::raw(" "));
+    }
+    if width >= labelled {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(session, Style::default().fg(theme.text)));
+    }
+    
+    let area = Rect { y: size.y + size.height / 2, height: size.height.min(1), ..size };
+    f.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), area);
+}
+
+fn phase_key(phase: TimerPhase) -> &'static str {
+    match phase {
+        TimerPhase::Focus => "phase.focus",
+        TimerPhase::ShortBreak => "phase.short_break",
+        TimerPhase::LongBreak => "phase.long_break",
+    }
+}
+
+/// A label as spans: the secondary language, then the primary one in
+/// brackets, or just the primary one.
+fn label_spans(locale: &Locale, key: &str, secondary: Style, primary: Style) -> Vec<Span<'static>> {
+    label_spans_with(locale, key, &[], secondary, primary)
+}
+
+fn label_spans_with(
+    locale: &Locale,
+    key: &str,
+    args: &[(&str, &dyn std::fmt::Display)],
+    secondary: Style,
+    primary: Style,
+) -> Vec<Span<'static>> {
+    match locale.pair_with(key, args) {
+        (Some(second), first) => vec![
+            Span::styled(second, secondary),
+            Span::styled(" [", primary),
+            Span::styled(first, primary),
+            Span::styled("]", primary),
+        ],
+        (None, first) => vec![Span::styled(first, primary)],
+    }
+}
+
+/// Pads `spans` with spaces to `width` display columns.
+fn pad_spans(mut spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let used: usize = spans.iter().map(|span| span.content.width()).sum();
+    spans.push(Span::raw(" ".repeat(width.saturating_sub(used))));
+    spans
+}
+
+fn session_text(timer: &PomodoroTimer) -> String {
+    format!("{:02}/{:02}", timer.cycle_count + 1, timer.cycles_before_long_break)
+}
+
+/// Overtime, the phase color while running, or the paused color.
+fn timer_color(timer: &PomodoroTimer, theme: &Theme) -> ratatui::style::Color {
+    if timer.current_timer.in_overtime() {
+        theme.overtime_color
+    } else if timer.is_running() {
+        get_phase_color(timer.current_timer.phase, theme)
+    } else {
+        theme.paused_color
+    }
+}
+
+fn render_header(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme, locale: &Locale) {
+    let phase_color = get_phase_color(timer.current_timer.phase, theme);
+    let session_text = session_text(timer);
     
     let branding = "DevChron";
-    let phase_section = format!(" {} [{}] ", phase_kanji, phase_name);
-    let session_section = format!(" {} [SESSION] ", session_text);
+    let phase_style = Style::default().fg(phase_color).add_modifier(Modifier::BOLD);
+    let phase = label_spans(locale, phase_key(timer.current_timer.phase), phase_style, phase_style);
+    let session = label_spans(
+        locale,
+        "header.session",
+        Style::default().fg(theme.text),
+        Style::default().fg(theme.text),
+    );
     
-    // Calculate separator width to balance the header
-    let used_width = branding.len() + phase_section.len() + session_section.len() + 4;
+    // Fill the gap between the phase and the session, in display columns
+    let spans_width = |spans: &[Span]| spans.iter().map(|span| span.content.width()).sum::<usize>();
+    let used_width = 1 + branding.width() + 3 + spans_width(&phase) + 2 + session_text.width() + 1 + spans_width(&session) + 1;
     let separator_width = (area.width as usize).saturating_sub(used_width);
     
-    let header_text = vec![
-        Line::from(vec![
-            Span::styled(" ", Style::default()),
-            Span::styled(branding, Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled(" │ ", Style::default().fg(theme.border)),
-            Span::styled(phase_kanji, Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled(" [", Style::default().fg(theme.text)),
-            Span::styled(phase_name, Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled("] ", Style::default().fg(theme.text)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                format!("{:─<width$}", "", width = separator_width),
-                Style::default().fg(theme.border)
-            ),
-            Span::styled(" ", Style::default()),
-            Span::styled(&session_text, Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled(" [", Style::default().fg(theme.text)),
-            Span::styled("SESSION", Style::default().fg(theme.text)),
-            Span::styled("] ", Style::default().fg(theme.text)),
-        ]),
+    let mut line = vec![
+        Span::styled(" ", Style::default()),
+        Span::styled(branding, Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+        Span::styled(" │ ", Style::default().fg(theme.border)),
     ];
+    line.extend(phase);
+    line.push(Span::styled(" ", Style::default()));
+    line.push(Span::styled("─".repeat(separator_width), Style::default().fg(theme.border)));
+    line.push(Span::styled(" ", Style::default()));
+    line.push(Span::styled(session_text, Style::default().fg(phase_color).add_modifier(Modifier::BOLD)));
+    line.push(Span::styled(" ", Style::default()));
+    line.extend(session);
+    line.push(Span::styled(" ", Style::default()));
+    
+    let header_text = vec![Line::from(line)];
     
     let header = Paragraph::new(header_text)
         .block(Block::default()
@@ -96,40 +268,81 @@ fn render_header(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme
     f.render_widget(header, area);
 }
 
-fn render_timer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme) {
-    let phase_color = if timer.is_running() {
-        get_phase_color(timer.current_timer.phase, theme)
-    } else {
-        theme.paused_color
-    };
+fn render_timer(f: &mut Frame, area: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let timer = &app.timer;
+    let theme = &app.theme;
+    let locale = &app.locale;
+    hitboxes.add(area, Target::Timer);
+    let phase_color = timer_color(timer, theme);
+    
+    // Big digits get whatever the progress bar and countdown leave over
+    let time_str = timer.current_timer.format_time();
+    let clock_lines = clock::big_lines(
+        app.clock_style,
+        &time_str,
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(6),
+    )
+    .unwrap_or_else(|| vec![String::new(), convert_to_fullwidth(&time_str)]);
+    
+    let clock_height = clock_lines.len() as u16;
+    let padding = area.height.saturating_sub(clock_height + 4) / 2;
     
     // Center the timer display
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(30),
-            Constraint::Length(5),  // Timer
-            Constraint::Length(3),  // Progress bar
-            Constraint::Percentage(30),
+            Constraint::Length(padding),
+            Constraint::Length(clock_height + 1),  // Timer and countdown
+            Constraint::Length(3),                 // Progress bar
+            Constraint::Min(0),
         ])
         .split(area);
     
-    // Large time display with full-width numbers
-    let time_str = timer.current_timer.format_time();
-    let full_width_time = convert_to_fullwidth(&time_str);
-    
-    let time_text = vec![
-        Line::from(""),
-        Line::from(
-            Span::styled(
-                full_width_time,
-                Style::default()
-                    .fg(phase_color)
-                    .add_modifier(Modifier::BOLD)
+    let mut time_text: Vec<Line> = clock_lines
+        .into_iter()
+        .map(|line| {
+            Line::from(
+                Span::styled(
+                    line,
+                    Style::default()
+                        .fg(phase_color)
+                        .add_modifier(Modifier::BOLD)
+                )
             )
-        ),
-        Line::from(""),
-    ];
+        })
+        .collect();
+    time_text.push(
+        match (app.seek_preview, app.auto_start_countdown) {
+            (Some(percent), _) => {
+                let duration = timer.current_timer.duration.as_secs();
+                let left = duration - duration * percent as u64 / 100;
+                let time = format!("{:02}:{:02}", left / 60, left % 60);
+                let style = Style::default().fg(theme.text).add_modifier(Modifier::ITALIC);
+                let mut spans = vec![Span::styled("◆ ", Style::default().fg(phase_color))];
+                spans.extend(label_spans_with(
+                    locale,
+                    "timer.seek_preview",
+                    &[("time", &time), ("percent", &percent)],
+                    style,
+                    style,
+                ));
+                Line::from(spans)
+            }
+            (None, Some(secs)) => {
+                let countdown = Style::default().fg(get_phase_color(timer.current_timer.phase, theme));
+                let cancel = Style::default().fg(theme.paused_color).add_modifier(Modifier::ITALIC);
+                let mut spans = label_spans_with(locale, "timer.starting_in", &[("seconds", &secs)], countdown, countdown);
+                // A postponed strict break can't be cancelled, only waited out
+                if !app.in_strict_break() {
+                    spans.push(Span::styled(format!("  {} ", key_badge(&app.keymap, Action::CancelAutoStart)), cancel));
+                    spans.extend(label_spans(locale, "timer.cancel", cancel, cancel));
+                }
+                Line::from(spans)
+            }
+            (None, None) => Line::from(""),
+        }
+    );
     
     let time_display = Paragraph::new(time_text)
         .alignment(Alignment::Center);
@@ -137,30 +350,67 @@ fn render_timer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme)
     f.render_widget(time_display, chunks[1]);
     
     // Progress bar with custom characters
-    render_progress_bar(f, chunks[2], timer, theme);
+    render_progress_bar(f, chunks[2], app, hitboxes);
 }
 
-fn render_progress_bar(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme) {
-    let phase_color = if timer.is_running() {
-        get_phase_color(timer.current_timer.phase, theme)
-    } else {
-        theme.paused_color
-    };
+fn render_progress_bar(f: &mut Frame, area: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let timer = &app.timer;
+    let theme = &app.theme;
+    let phase_color = timer_color(timer, theme);
+    
+    // Drop the label before squeezing the bar below 20 columns
+    let text_style = Style::default().fg(theme.text);
+    let mut label = label_spans(&app.locale, "timer.progress", text_style, text_style);
+    label.push(Span::styled("  ", text_style));
+    let label_width: usize = label.iter().map(|span| span.content.width()).sum();
+    let show_label = area.width as usize >= label_width + 36;
+    let margin = if show_label { 8 } else { 1 };
+    let label_width = if show_label { label_width } else { 0 };
     
     let progress = timer.current_timer.percentage_complete();
-    let bar_width = (area.width as usize).saturating_sub(40);
+    let bar_width = (area.width as usize).saturating_sub(2 * margin + label_width + 1);
     let filled = (bar_width * progress as usize) / 100;
     
-    let progress_bar = "━".repeat(filled) + "◯" + &"─".repeat(bar_width.saturating_sub(filled));
+    let filled_style = Style::default().fg(theme.progress_filled.unwrap_or(phase_color));
+    let empty_style = Style::default().fg(theme.progress_empty.unwrap_or(phase_color));
+    let preview = app.seek_preview.map(|percent| bar_width * percent as usize / 100);
     
-    let progress_text = vec![
-        Line::from(vec![
-            Span::styled("        進捗 ", Style::default().fg(theme.text)),
-            Span::styled("[PROGRESS]  ", Style::default().fg(theme.text)),
-            Span::styled(&progress_bar, Style::default().fg(phase_color)),
-            Span::styled("        ", Style::default()),
-        ]),
-    ];
+    let mut spans = Vec::new();
+    if show_label {
+        spans.push(Span::styled(" ".repeat(margin), Style::default()));
+        spans.extend(label);
+    }
+    match preview {
+        // Draw cell by cell to fit the preview marker in
+        Some(marker) => spans.extend((0..=bar_width).map(|i| {
+            let (symbol, style) = match i {
+                _ if i == marker => ("◆", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+                _ if i < filled => ("━", filled_style),
+                _ if i == filled => ("◯", filled_style),
+                _ => ("─", empty_style),
+            };
+            Span::styled(symbol, style)
+        })),
+        None => {
+            spans.push(Span::styled("━".repeat(filled) + "◯", filled_style));
+            spans.push(Span::styled("─".repeat(bar_width.saturating_sub(filled)), empty_style));
+        }
+    }
+    if show_label {
+        spans.push(Span::styled(" ".repeat(margin), Style::default()));
+    }
+    
+    // The line is centered, so the track starts after half the slack
+    let line_width = if show_label { 2 * margin + label_width } else { 0 } + bar_width + 1;
+    let track_x = area.x as usize
+        + (area.width as usize).saturating_sub(line_width) / 2
+        + if show_label { margin + label_width } else { 0 };
+    hitboxes.add(
+        Rect { x: track_x as u16, y: area.y, width: (bar_width + 1) as u16, height: area.height.min(1) },
+        Target::ProgressBar,
+    );
+    
+    let progress_text = vec![Line::from(spans)];
     
     let progress = Paragraph::new(progress_text)
         .alignment(Alignment::Center);
@@ -168,7 +418,12 @@ fn render_progress_bar(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme:
     f.render_widget(progress, area);
 }
 
-fn render_footer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme) {
+fn render_footer(f: &mut Frame, area: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let timer = &app.timer;
+    let theme = &app.theme;
+    let locale = &app.locale;
+    let keymap = &app.keymap;
+    
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -177,31 +432,40 @@ fn render_footer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme
         ])
         .split(area);
     
-    // Statistics
-    let stats_text = vec![
-        Line::from(vec![
-            Span::styled(" 統計 ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
-            Span::styled("[STATISTICS]", Style::default().fg(theme.text)),
-        ]),
+    let text = Style::default().fg(theme.text);
+    let heading = text.add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD);
+    
+    // Statistics, with values lined up after the longest label
+    let mut stats = vec![
+        ("stats.daily", timer.stats.format_today_time()),
+        ("stats.done", format!("{:02}", timer.stats.sessions_completed)),
+        ("stats.streak", format!("{:02}", timer.cycle_count)),
+    ];
+    if let Some(score) = app.focus_score() {
+        stats.push(("stats.score", format!("{}%", score)));
+    }
+    let label_width = stats
+        .iter()
+        .map(|(key, _)| locale.label(key).width())
+        .max()
+        .unwrap_or(0);
+    
+    let mut title = vec![Span::styled(" ", heading)];
+    title.extend(label_spans(locale, "stats.title", heading, text));
+    let mut stats_text = vec![
+        Line::from(title),
         Line::from(
             Span::styled(" ━━━━━━━━━━━━", Style::default().fg(theme.border))
         ),
-        Line::from(vec![
-            Span::styled(" 今日 ", Style::default().fg(theme.text)),
-            Span::styled("[Daily]  ", Style::default().fg(theme.text)),
-            Span::styled(timer.stats.format_today_time(), Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(vec![
-            Span::styled(" 完了 ", Style::default().fg(theme.text)),
-            Span::styled("[Done]   ", Style::default().fg(theme.text)),
-            Span::styled(format!("{:02}", timer.stats.sessions_completed), Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(vec![
-            Span::styled(" 連勝 ", Style::default().fg(theme.text)),
-            Span::styled("[Streak] ", Style::default().fg(theme.text)),
-            Span::styled(format!("{:02}", timer.cycle_count), Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-        ]),
     ];
+    for (key, stat) in stats {
+        let mut spans = vec![Span::styled(" ", text)];
+        spans.extend(label_spans(locale, key, text, text));
+        let mut spans = pad_spans(spans, label_width + 2);
+        spans.push(Span::styled(stat, value));
+        stats_text.push(Line::from(spans));
+    }
     
     let stats = Paragraph::new(stats_text)
         .block(Block::default()
@@ -213,32 +477,50 @@ fn render_footer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme
     f.render_widget(stats, chunks[0]);
     
     // Controls
-    let state_text = if timer.is_running() { "静 [Pause]" } else { "再開 [Start]" };
+    let state_key = if timer.is_running() { "controls.pause" } else { "controls.start" };
     let phase_color = get_phase_color(timer.current_timer.phase, theme);
+    let badge = Style::default().fg(phase_color).add_modifier(Modifier::BOLD);
+    
+    let control = |prefix: &str, action: Action, key: &str| {
+        let mut spans = vec![Span::styled(format!("{}{} ", prefix, key_badge(keymap, action)), badge)];
+        spans.extend(label_spans(locale, key, text, text));
+        spans
+    };
+    
+    let mut title = vec![Span::styled(" ", heading)];
+    title.extend(label_spans(locale, "controls.title", heading, text));
+    let skip = control(" ", Action::Skip, "controls.skip");
+    let skip_width: usize = skip.iter().map(|span| span.content.width()).sum();
+    let skip_quit_gap = (skip_width + 2).max(25);
+    let mut skip_quit = pad_spans(skip, skip_quit_gap);
+    let quit = control("", Action::Quit, "controls.quit");
+    let quit_width: usize = quit.iter().map(|span| span.content.width()).sum();
+    skip_quit.extend(quit);
+    
+    // Buttons sit below the top border, the title and the rule
+    let row = |line: u16| chunks[1].y + 1 + line;
+    let button = |line: u16, x: usize, width: usize| Rect {
+        x: chunks[1].x + x as u16,
+        y: row(line),
+        width: (width as u16).min(chunks[1].width.saturating_sub(x as u16)),
+        height: 1,
+    };
+    let width_of = |spans: Vec<Span>| spans.iter().map(|span| span.content.width()).sum::<usize>();
+    if row(4) < chunks[1].bottom() {
+        hitboxes.add(button(2, 0, width_of(control(" ", Action::TogglePause, state_key))), Target::Button(Action::TogglePause));
+        hitboxes.add(button(3, 0, width_of(control(" ", Action::Reset, "controls.reset"))), Target::Button(Action::Reset));
+        hitboxes.add(button(4, 0, skip_width), Target::Button(Action::Skip));
+        hitboxes.add(button(4, skip_quit_gap, quit_width), Target::Button(Action::Quit));
+    }
     
     let controls_text = vec![
-        Line::from(vec![
-            Span::styled(" 操作 ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
-            Span::styled("[CONTROLS]", Style::default().fg(theme.text)),
-        ]),
+        Line::from(title),
         Line::from(
             Span::styled(" ━━━━━━━━━━━━", Style::default().fg(theme.border))
         ),
-        Line::from(vec![
-            Span::styled(" ［ｐ］ ", Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled(state_text, Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled(" ［ｒ］ ", Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled("戻 [Reset]", Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled(" ［ｓ］ ", Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled("進 [Skip] ", Style::default().fg(theme.text)),
-            Span::styled("       ", Style::default()),
-            Span::styled("［ｑ］ ", Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
-            Span::styled("終 [Quit]", Style::default().fg(theme.text)),
-        ]),
+        Line::from(control(" ", Action::TogglePause, state_key)),
+        Line::from(control(" ", Action::Reset, "controls.reset")),
+        Line::from(skip_quit),
     ];
     
     let controls = Paragraph::new(controls_text)
@@ -251,56 +533,398 @@ fn render_footer(f: &mut Frame, area: Rect, timer: &PomodoroTimer, theme: &Theme
     f.render_widget(controls, chunks[1]);
 }
 
-fn render_help(f: &mut Frame, area: Rect, theme: &Theme) {
-    let help_text = vec![
+fn render_break_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let timer = &app.timer;
+    let phase_color = get_phase_color(timer.current_timer.phase, theme);
+    
+    let mut lines = vec![
         Line::from(""),
         Line::from(
             Span::styled(
-                "DevChron - Pomodoro Timer",
-                Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)
+                app.locale.label("break.title"),
+                Style::default().fg(phase_color).add_modifier(Modifier::BOLD)
             )
         ),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Space/P   ", Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled("Start/Pause timer", Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled("R         ", Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled("Reset current timer", Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled("S         ", Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled("Skip to next phase", Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled("Q / Esc   ", Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled("Quit application", Style::default().fg(theme.text)),
-        ]),
-        Line::from(vec![
-            Span::styled("H / ?     ", Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD)),
-            Span::styled("Toggle this help screen", Style::default().fg(theme.text)),
-        ]),
+        Line::from(
+            Span::styled(
+                convert_to_fullwidth(&timer.current_timer.format_time()),
+                Style::default().fg(phase_color).add_modifier(Modifier::BOLD)
+            )
+        ),
         Line::from(""),
         Line::from(
             Span::styled(
-                "Press any key to return...",
-                Style::default().fg(theme.paused_color).add_modifier(Modifier::ITALIC)
+                app.locale.text("break.step_away"),
+                Style::default().fg(theme.text)
             )
         ),
+        Line::from(""),
+    ];
+    
+    if let Some(strict) = &app.strict_breaks {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", key_badge(&app.keymap, Action::PostponeBreak)), Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(
+                    "{} {}",
+                    app.locale.label_with("break.postpone", &[("minutes", &strict.postpone_minutes())]),
+                    app.locale.format("break.left_today", &[("count", &strict.postpones_left())])
+                ),
+                Style::default().fg(theme.text)
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", key_badge(&app.keymap, Action::EmergencySkip)), Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(
+                    "{} {}",
+                    app.locale.label("break.emergency_skip"),
+                    app.locale.format("break.left_today", &[("count", &strict.emergency_skips_left())])
+                ),
+                Style::default().fg(theme.text)
+            ),
+        ]));
+    }
+    
+    let overlay = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(phase_color))
+                .style(Style::default().bg(theme.background))
+        )
+        .alignment(Alignment::Center);
+    
+    f.render_widget(Clear, area);
+    f.render_widget(overlay, area);
+}
+
+/// The help as a popup over the live screen. Scrolls when the bindings
+/// don't fit.
+fn render_help(f: &mut Frame, area: Rect, app: &App, hitboxes: &mut Hitboxes) {
+    let theme = &app.theme;
+    let locale = &app.locale;
+    let key_style = Style::default().fg(theme.focus_color).add_modifier(Modifier::BOLD);
+    let entries: Vec<(String, String)> = app
+        .keymap
+        .bindings()
+        .map(|binding| {
+            let keys: Vec<String> = binding.keys.iter().map(ToString::to_string).collect();
+            (keys.join(" / "), help_description(locale, binding))
+        })
+        .collect();
+    // Pad both columns by display width so the centered rows line up
+    let key_width = entries.iter().map(|(keys, _)| keys.width()).max().unwrap_or(0) + 2;
+    let description_width = entries.iter().map(|(_, text)| text.width()).max().unwrap_or(0);
+    let pad = |text: &str, width: usize| format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())));
+    
+    let heading = locale.text("help.heading");
+    let mut help_text = vec![
+        Line::from(""),
+        Line::from(
+            Span::styled(heading.clone(), key_style)
+        ),
+        Line::from(""),
     ];
+    for (keys, description) in entries {
+        help_text.push(Line::from(vec![
+            Span::styled(pad(&keys, key_width), key_style),
+            Span::styled(pad(&description, description_width), Style::default().fg(theme.text)),
+        ]));
+    }
+    help_text.push(Line::from(""));
+    
+    // Borders plus a column of padding on each side
+    let dismiss = locale.text("help.dismiss");
+    let scroll_hint = locale.text("help.scroll");
+    let content_width = (key_width + description_width)
+        .max(heading.width())
+        .max(dismiss.width().max(scroll_hint.width()) + 2);
+    let popup = centered_rect(
+        area,
+        content_width as u16 + 4,
+        help_text.len() as u16 + 2,
+    );
+    
+    let visible = popup.height.saturating_sub(2);
+    let scroll_max = (help_text.len() as u16).saturating_sub(visible);
+    app.help_scroll_max.set(scroll_max);
+    let scroll = app.help_scroll.min(scroll_max);
+    let hint = if scroll_max > 0 { scroll_hint } else { dismiss };
     
     let help = Paragraph::new(help_text)
         .block(
             Block::default()
-                .title("Help")
+                .title(locale.text("help.title"))
+                .title(
+                    Title::from(Span::styled(
+                        format!(" {} ", hint),
+                        Style::default().fg(theme.paused_color).add_modifier(Modifier::ITALIC)
+                    ))
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center)
+                )
                 .borders(Borders::ALL)
                 .border_type(BorderType::Double)
                 .border_style(Style::default().fg(theme.border))
+                .style(Style::default().bg(theme.background))
         )
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+        .scroll((scroll, 0));
+    
+    f.render_widget(Clear, popup);
+    f.render_widget(help, popup);
     
-    f.render_widget(help, area);
+    // A click anywhere closes the help, the wheel scrolls it
+    *hitboxes = Hitboxes::default();
+    hitboxes.add(area, Target::Button(Action::ToggleHelp));
+    hitboxes.add(popup, Target::Help);
+}
+
+/// Recent messages in the top-right corner, colored by severity. Too
+/// short a screen gets just the newest one, without a frame.
+fn render_toasts(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let toasts = &app.toasts;
+    if toasts.is_empty() || area.width < 8 {
+        return;
+    }
+    
+    let color = |level: Level| match level {
+        Level::Debug | Level::Info => theme.info_color,
+        Level::Warning => theme.warning_color,
+        Level::Error => theme.error_color,
+    };
+    let icon = |level: Level| match level {
+        Level::Debug | Level::Info => "ℹ",
+        Level::Warning => "⚠",
+        Level::Error => "✖",
+    };
+    let text = |toast: &Toast| match toast.repeats {
+        0 => format!("{} {}", icon(toast.level), toast.text),
+        repeats => format!("{} {} (×{})", icon(toast.level), toast.text, repeats + 1),
+    };
+    
+    let framed = area.height >= toasts.iter().count() as u16 + 2;
+    let shown: Vec<&Toast> = if framed {
+        toasts.iter().collect()
+    } else {
+        toasts.iter().rev().take(1).collect()
+    };
+    let max_width = if framed { area.width.saturating_sub(4).min(60) } else { area.width } as usize;
+    let lines: Vec<Line> = shown
+        .iter()
+        .map(|toast| {
+            Line::from(Span::styled(
+                truncate(&text(toast), max_width),
+                Style::default().fg(color(toast.level)).add_modifier(Modifier::BOLD),
+            ))
+        })
+        .collect();
+    let content_width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16;
+    
+    if !framed {
+        let line = Rect { x: area.right() - content_width, y: area.y, width: content_width, height: 1 };
+        f.render_widget(Clear, line);
+        f.render_widget(Paragraph::new(lines).style(Style::default().bg(theme.background)), line);
+        return;
+    }
+    
+    // Clear of the outer border, so the frame underneath stays readable
+    let width = content_width + 4;
+    let height = lines.len() as u16 + 2;
+    let inset = u16::from(area.width >= width + 2 && area.height >= height + 2);
+    let popup = Rect {
+        x: area.right() - width - inset,
+        y: area.y + inset,
+        width,
+        height,
+    };
+    let worst = shown.iter().map(|toast| toast.level).max().unwrap_or(Level::Info);
+    
+    let messages = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(worst)))
+            .padding(Padding::horizontal(1))
+            .style(Style::default().bg(theme.background))
+    );
+    f.render_widget(Clear, popup);
+    f.render_widget(messages, popup);
+}
+
+/// Cuts `text` to `width` columns, marking the cut with `…`.
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    for c in text.chars() {
+        if cut.width() + c.to_string().width() + 1 > width {
+            break;
+        }
+        cut.push(c);
+    }
+    cut.push('…');
+    cut
+}
+
+/// Most suggestions listed under the palette input.
+const PALETTE_SUGGESTIONS: usize = 6;
+
+/// The `:` command line, docked at the bottom over the live screen.
+fn render_palette(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let locale = &app.locale;
+    let palette = &app.palette;
+    let phase_color = get_phase_color(app.timer.current_phase(), theme);
+    let text = Style::default().fg(theme.text);
+    let dim = Style::default().fg(theme.paused_color);
+    
+    let mut lines = vec![Line::from(vec![
+        Span::styled(":", Style::default().fg(phase_color).add_modifier(Modifier::BOLD)),
+        Span::styled(palette.input.clone(), text),
+        Span::styled("█", Style::default().fg(phase_color)),
+    ])];
+    
+    if let Some(error) = &palette.error {
+        lines.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(theme.overtime_color).add_modifier(Modifier::BOLD),
+        )));
+    }
+    
+    let (suggestions, selected) = palette.suggestions();
+    let entries: Vec<(String, String)> = suggestions
+        .iter()
+        .map(|suggestion| {
+            let usage = suggestion.usage.map(|usage| format!(" {}", usage)).unwrap_or_default();
+            (
+                format!("{}{}", suggestion.text.trim_end(), usage),
+                command_description(locale, suggestion.command),
+            )
+        })
+        .collect();
+    let name_width = entries.iter().map(|(name, _)| name.width()).max().unwrap_or(0) + 2;
+    
+    // Keep the selected suggestion in view
+    let first = selected
+        .map(|index| (index + 1).saturating_sub(PALETTE_SUGGESTIONS))
+        .unwrap_or(0);
+    for (index, (name, description)) in entries.iter().enumerate().skip(first).take(PALETTE_SUGGESTIONS) {
+        let is_selected = selected.unwrap_or(0) == index;
+        let (marker, name_style) = if is_selected {
+            ("▸ ", Style::default().fg(phase_color).add_modifier(Modifier::BOLD))
+        } else {
+            ("  ", text)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, name_style),
+            Span::styled(format!("{}{}", name, " ".repeat(name_width.saturating_sub(name.width()))), name_style),
+            Span::styled(description.clone(), dim),
+        ]));
+    }
+    if entries.is_empty() && palette.error.is_none() && !palette.input.trim().is_empty() {
+        lines.push(Line::from(Span::styled(
+            locale.text("palette.no_match"),
+            dim.add_modifier(Modifier::ITALIC),
+        )));
+    }
+    
+    let width = area.width.min(72);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.bottom() - height,
+        width,
+        height,
+    };
+    
+    let command_line = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" {} ", locale.text("palette.title")))
+                .title(
+                    Title::from(Span::styled(
+                        format!(" {} ", locale.text("palette.hint")),
+                        dim.add_modifier(Modifier::ITALIC)
+                    ))
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center)
+                )
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(phase_color))
+                .style(Style::default().bg(theme.background))
+        );
+    
+    f.render_widget(Clear, popup);
+    f.render_widget(command_line, popup);
+}
+
+/// One-line description of a palette command.
+fn command_description(locale: &Locale, command: &str) -> String {
+    let name = command.replace('-', "_");
+    let palette_key = format!("palette.{}", name);
+    match locale.text(&palette_key) {
+        text if text != palette_key => text,
+        _ => locale.text(&format!("help.{}", name)),
+    }
+}
+
+/// A `width` x `height` rect centered in `area`, shrunk to fit.
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// What a binding does, from the catalog; commands without an entry
+/// are shown as typed.
+fn help_description(locale: &Locale, binding: &Binding) -> String {
+    if let Action::Adjust(secs) = binding.action {
+        let key = if secs > 0 { "help.extend" } else { "help.shorten" };
+        let amount = if secs % 60 == 0 {
+            locale.format("help.minutes", &[("count", &(secs.abs() / 60))])
+        } else {
+            locale.format("help.seconds", &[("count", &secs.abs())])
+        };
+        return locale.format(key, &[("amount", &amount)]);
+    }
+    
+    let name = binding.command.split_whitespace().next().unwrap_or_default();
+    let key = format!("help.{}", name.replace('-', "_"));
+    match locale.text(&key) {
+        text if text == key => binding.command.clone(),
+        text => text,
+    }
+}
+
+/// `［ｐ］` for the key bound to `action`, empty when it has none.
+fn key_badge(keymap: &Keymap, action: Action) -> String {
+    match keymap.key_for(action) {
+        Some(key) => {
+            let key = key.to_string();
+            match key.chars().count() {
+                // Printable ASCII has a fullwidth form at a fixed offset
+                1 => format!("［{}］", key.chars().map(|c| match c {
+                    '!'..='~' => char::from_u32(c as u32 + 0xFEE0).unwrap_or(c),
+                    _ => c,
+                }).collect::<String>()),
+                _ => format!("［{}］", key),
+            }
+        }
+        None => String::new(),
+    }
 }
 
 fn get_phase_color(phase: TimerPhase, theme: &Theme) -> ratatui::style::Color {
@@ -324,6 +948,7 @@ fn convert_to_fullwidth(s: &str) -> String {
         '8' => '８',
         '9' => '９',
         ':' => '：',
+        '+' => '＋',
         ' ' => ' ',
         _ => c,
     }).collect()
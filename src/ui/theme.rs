@@ -1,4 +1,12 @@
+use std::fs;
+use std::path::PathBuf;
+use directories::ProjectDirs;
 use ratatui::style::Color;
+use serde::Deserialize;
+use crate::config::settings::{ThemeColors, UiSettings};
+use crate::error::{Error, Result};
+
+pub const BUILTIN_THEMES: &[&str] = &["nord", "catppuccin", "classic"];
 
 #[derive(Debug, Clone, Copy)]
 pub struct Theme {
@@ -6,8 +14,29 @@ pub struct Theme {
     pub short_break_color: Color,
     pub long_break_color: Color,
     pub paused_color: Color,
+    pub overtime_color: Color,
     pub text: Color,
     pub border: Color,
+    pub background: Color,
+    pub header: Color,
+    /// `None` follows the phase color
+    pub progress_filled: Option<Color>,
+    pub progress_empty: Option<Color>,
+    /// Toast colors by severity
+    pub info_color: Color,
+    pub warning_color: Color,
+    pub error_color: Color,
+}
+
+/// A theme file in `~/.config/devchron/themes/`.
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    /// Built-in theme supplying the colors the file leaves out
+    #[serde(default)]
+    base: Option<String>,
+
+    #[serde(flatten)]
+    colors: ThemeColors,
 }
 
 impl Theme {
@@ -17,8 +46,16 @@ impl Theme {
             short_break_color: Color::Rgb(163, 190, 140), // Aurora green
             long_break_color: Color::Rgb(129, 161, 193),  // Frost blue
             paused_color: Color::Rgb(216, 222, 233),      // Snow storm
+            overtime_color: Color::Rgb(235, 203, 139),    // Aurora yellow
             text: Color::Rgb(236, 239, 244),              // Snow storm
             border: Color::Rgb(76, 86, 106),              // Polar night lighter
+            background: Color::Reset,
+            header: Color::Rgb(191, 97, 106),
+            progress_filled: None,
+            progress_empty: None,
+            info_color: Color::Rgb(136, 192, 208),        // Frost cyan
+            warning_color: Color::Rgb(235, 203, 139),     // Aurora yellow
+            error_color: Color::Rgb(191, 97, 106),        // Aurora red
         }
     }
 
@@ -28,8 +65,16 @@ impl Theme {
             short_break_color: Color::Rgb(166, 227, 161), // Green
             long_break_color: Color::Rgb(137, 180, 250),  // Blue
             paused_color: Color::Rgb(186, 194, 222),      // Overlay2
+            overtime_color: Color::Rgb(250, 179, 135),    // Peach
             text: Color::Rgb(205, 214, 244),              // Text
             border: Color::Rgb(88, 91, 112),              // Surface2
+            background: Color::Reset,
+            header: Color::Rgb(243, 139, 168),
+            progress_filled: None,
+            progress_empty: None,
+            info_color: Color::Rgb(137, 220, 235),        // Sky
+            warning_color: Color::Rgb(249, 226, 175),     // Yellow
+            error_color: Color::Rgb(243, 139, 168),       // Red
         }
     }
 
@@ -39,16 +84,187 @@ impl Theme {
             short_break_color: Color::Rgb(40, 167, 69),   // Green
             long_break_color: Color::Rgb(0, 123, 255),    // Blue
             paused_color: Color::Rgb(108, 117, 125),      // Gray
+            overtime_color: Color::Rgb(253, 126, 20),     // Orange
             text: Color::Rgb(248, 249, 250),              // Light
             border: Color::Rgb(73, 80, 87),               // Border
+            background: Color::Reset,
+            header: Color::Rgb(220, 53, 69),
+            progress_filled: None,
+            progress_empty: None,
+            info_color: Color::Rgb(23, 162, 184),         // Info
+            warning_color: Color::Rgb(255, 193, 7),       // Warning
+            error_color: Color::Rgb(220, 53, 69),         // Danger
         }
     }
 
-    pub fn from_name(name: &str) -> Self {
+    fn builtin(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
-            "catppuccin" | "mocha" => Self::catppuccin(),
-            "classic" | "tomato" => Self::classic(),
-            _ => Self::nord(),
+            "nord" => Some(Self::nord()),
+            "catppuccin" | "mocha" => Some(Self::catppuccin()),
+            "classic" | "tomato" => Some(Self::classic()),
+            _ => None,
+        }
+    }
+
+    /// The theme selected in `[ui]`, with `[ui.custom_theme]` applied.
+    pub fn load(settings: &UiSettings) -> Result<Self> {
+        let mut theme = Self::from_name(&settings.theme)?;
+        if let Some(colors) = &settings.custom_theme {
+            theme
+                .apply(colors)
+                .map_err(|e| Error::Config(format!("[ui.custom_theme]: {}", e)))?;
+        }
+        Ok(theme)
+    }
+
+    /// A built-in theme or one loaded from the themes directory.
+    pub fn from_name(name: &str) -> Result<Self> {
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+
+        // The name becomes a file name, so it must not reach outside the
+        // themes directory
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(Error::Config(format!(
+                "invalid theme name `{}`, names can't contain path separators or `..`",
+                name
+            )));
+        }
+
+        let path = themes_dir()
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.exists())
+            .ok_or_else(|| Error::Config(format!(
+                "unknown theme `{}`, see `devchron themes list`",
+                name
+            )))?;
+
+        let content = fs::read_to_string(&path)?;
+        let file: ThemeFile = toml::from_str(&content)
+            .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+
+        let mut theme = match file.base.as_deref() {
+            Some(base) => Self::builtin(base).ok_or_else(|| Error::Config(format!(
+                "{}: unknown base theme `{}`, expected one of: {}",
+                path.display(),
+                base,
+                BUILTIN_THEMES.join(", ")
+            )))?,
+            None => Self::nord(),
+        };
+        theme
+            .apply(&file.colors)
+            .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+        Ok(theme)
+    }
+
+    fn apply(&mut self, colors: &ThemeColors) -> std::result::Result<(), String> {
+        let fields = [
+            (&colors.focus_color, &mut self.focus_color),
+            (&colors.short_break_color, &mut self.short_break_color),
+            (&colors.long_break_color, &mut self.long_break_color),
+            (&colors.paused_color, &mut self.paused_color),
+            (&colors.overtime_color, &mut self.overtime_color),
+            (&colors.text, &mut self.text),
+            (&colors.border, &mut self.border),
+            (&colors.background, &mut self.background),
+            (&colors.header, &mut self.header),
+            (&colors.info_color, &mut self.info_color),
+            (&colors.warning_color, &mut self.warning_color),
+            (&colors.error_color, &mut self.error_color),
+        ];
+        for (value, color) in fields {
+            if let Some(value) = value {
+                *color = parse_color(value)?;
+            }
+        }
+
+        if let Some(value) = &colors.progress_filled {
+            self.progress_filled = Some(parse_color(value)?);
         }
+        if let Some(value) = &colors.progress_empty {
+            self.progress_empty = Some(parse_color(value)?);
+        }
+        Ok(())
+    }
+}
+
+fn parse_color(value: &str) -> std::result::Result<Color, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid color `{}`", value))
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("", "", "devchron")?;
+    Some(proj_dirs.config_dir().join("themes"))
+}
+
+/// Names of the theme files in the themes directory, sorted.
+pub fn user_themes() -> Vec<String> {
+    let Some(entries) = themes_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// `#rrggbb` form of a theme color, for status bars and compositors.
+pub fn to_hex(color: Color) -> String {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        // Common xterm values for the named colors
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        _ => (255, 255, 255),
+    };
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_names_ignore_case() {
+        assert_eq!(Theme::from_name("Mocha").unwrap().focus_color, Theme::catppuccin().focus_color);
+    }
+
+    #[test]
+    fn names_cannot_leave_the_themes_dir() {
+        for name in ["../../x", "/etc/passwd", "sub/theme", "..", "a\\b", ""] {
+            let error = Theme::from_name(name).expect_err(name).to_string();
+            assert!(error.contains("invalid theme name"), "{}: {}", name, error);
+        }
+    }
+
+    #[test]
+    fn unknown_theme_is_reported() {
+        let error = Theme::from_name("devchron-no-such-theme").unwrap_err().to_string();
+        assert!(error.contains("unknown theme"));
     }
 }
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use crate::log::{Level, Message};
+
+/// Toasts on screen at once; the oldest makes room for a new one.
+const MAX_TOASTS: usize = 3;
+
+/// A message shown in the corner for a few seconds.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: Level,
+    pub text: String,
+    /// How many more times the same message came in while shown
+    pub repeats: u32,
+    expires: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Shows a message. A repeat of one already on screen bumps its count
+    /// and timer rather than stacking, so a failing sink can't fill the
+    /// corner.
+    pub fn push(&mut self, message: Message) {
+        let expires = Instant::now() + lifetime(message.level);
+        if let Some(toast) = self.toasts.iter_mut().find(|toast| toast.text == message.text) {
+            toast.repeats += 1;
+            toast.expires = expires;
+            return;
+        }
+
+        self.toasts.push_back(Toast {
+            level: message.level,
+            text: message.text,
+            repeats: 0,
+            expires,
+        });
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    pub fn expire(&mut self) {
+        let now = Instant::now();
+        self.toasts.retain(|toast| toast.expires > now);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Errors stay up longest, they're the ones worth reading.
+fn lifetime(level: Level) -> Duration {
+    Duration::from_secs(match level {
+        Level::Debug | Level::Info => 4,
+        Level::Warning => 8,
+        Level::Error => 12,
+    })
+}